@@ -0,0 +1,2 @@
+pub mod import_ceps;
+pub mod reindex_phonetics;
@@ -0,0 +1,35 @@
+use std::{error::Error, path::PathBuf};
+
+use cep_service::import;
+use database::{models::cep_address::CepAddressModel, pool::connect};
+
+#[derive(Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// Community CSV dump with a header row.
+    Csv,
+    /// Directory with the delimited Correios e-DNE files.
+    Edne,
+}
+
+/// Imports or refreshes the offline CEP dataset used by the `database` provider.
+pub async fn import_ceps(
+    path: PathBuf,
+    format: ImportFormat,
+    delimiter: char,
+) -> Result<(), Box<dyn Error>> {
+    let imported = match format {
+        ImportFormat::Csv => import::read_csv(&path, delimiter as u8)?,
+        ImportFormat::Edne => import::read_edne(&path)?,
+    };
+    println!(
+        "Read {} CEP records from {}, skipped {} with an invalid CEP.",
+        imported.records.len(),
+        path.display(),
+        imported.skipped
+    );
+
+    let connection = connect().await?;
+    let affected = CepAddressModel::upsert_many(&imported.records, &connection).await?;
+    println!("Imported CEP records ({} rows affected).", affected);
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use std::error::Error;
+
+use database::{models::person::PersonModel, pool::connect};
+
+/// Fills in the phonetic name keys used by duplicate detection for persons created before
+/// they existed.
+pub async fn reindex_phonetics() -> Result<(), Box<dyn Error>> {
+    let connection = connect().await?;
+    let updated = PersonModel::reindex_phonetics(&connection).await?;
+    println!("Reindexed phonetic keys ({} rows affected).", updated);
+    Ok(())
+}
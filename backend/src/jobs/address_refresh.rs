@@ -0,0 +1,56 @@
+use std::{env, time::Duration};
+
+use cep_service::error::CepServiceError;
+use chrono::Utc;
+use database::models::{address::AddressModel, person::ResolvedAddress};
+use tokio::task::JoinHandle;
+
+use crate::state::ApplicationState;
+
+const BATCH_SIZE: u32 = 100;
+
+/// Periodically re-resolves addresses that were never verified or are older than
+/// `ADDRESS_STALE_AFTER_SECS`, so reads can rely on the stored columns alone.
+pub fn spawn(state: ApplicationState) -> JoinHandle<()> {
+    let interval = Duration::from_secs(env_secs("ADDRESS_REFRESH_INTERVAL_SECS", 3600));
+    let stale_after = Duration::from_secs(env_secs("ADDRESS_STALE_AFTER_SECS", 2_592_000));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(error) = refresh_stale(&state, stale_after).await {
+                eprintln!("Address refresh failed: {}", error);
+            }
+        }
+    })
+}
+
+async fn refresh_stale(state: &ApplicationState, stale_after: Duration) -> sqlx::Result<()> {
+    let verified_before =
+        Utc::now() - chrono::Duration::from_std(stale_after).unwrap_or(chrono::Duration::days(30));
+    let addresses =
+        AddressModel::list_stale(verified_before, BATCH_SIZE, &state.database_connection).await?;
+    for address in addresses {
+        let resolved = match state.cep_service.get_address(address.cep).await {
+            Ok(address) => ResolvedAddress::from(address),
+            // The CEP no longer exists upstream: keep the last known address but mark it checked
+            // so it is not retried on every tick.
+            Err(CepServiceError::NotFound) => ResolvedAddress {
+                address_verified_at: Some(Utc::now()),
+                ..address.resolved.clone()
+            },
+            Err(_) => continue,
+        };
+        address
+            .update_resolved(&resolved, &state.database_connection)
+            .await?;
+    }
+    Ok(())
+}
+
+fn env_secs(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use database::models::{
+    address::AddressModel, annotation::AnnotationModel, person::PersonModel,
+    person::ResolvedAddress,
+};
+use futures::{stream, StreamExt};
+
+use crate::{
+    objects::{address::PersonAddress, annotation::Annotation, person::Person},
+    state::ApplicationState,
+};
+
+/// Upper bound on CEP lookups in flight while loading a single page.
+const RESOLVE_CONCURRENCY: usize = 8;
+
+/// Builds `Person` responses for a page of persons with one query for their addresses and one
+/// for their annotations. Addresses that were never verified are resolved concurrently for the
+/// response only; storing the result is left to `jobs::address_refresh`, so reads never write.
+pub async fn load_persons(
+    person_models: Vec<PersonModel>,
+    state: &ApplicationState,
+) -> sqlx::Result<Vec<Person>> {
+    let person_ids: Vec<u64> = person_models.iter().map(|person| person.id).collect();
+    let (addresses, annotations) = futures::join!(
+        AddressModel::list_by_persons(&person_ids, &state.database_connection),
+        AnnotationModel::list_by_persons(&person_ids, &state.database_connection),
+    );
+    let addresses = resolve_unverified(addresses?, state).await;
+
+    let mut addresses_by_person: HashMap<u64, Vec<AddressModel>> = HashMap::new();
+    for address in addresses {
+        addresses_by_person
+            .entry(address.person_id)
+            .or_default()
+            .push(address);
+    }
+    let mut annotations_by_person: HashMap<u64, Vec<Annotation>> = HashMap::new();
+    for annotation in annotations? {
+        annotations_by_person
+            .entry(annotation.person_id)
+            .or_default()
+            .push(Annotation::from(annotation));
+    }
+
+    let persons = person_models
+        .into_iter()
+        .map(|mut person_model| {
+            let addresses = addresses_by_person
+                .remove(&person_model.id)
+                .unwrap_or_default();
+            if let Some(primary) = addresses.iter().find(|address| address.is_primary) {
+                if !person_model.address.is_verified() && Some(primary.cep) == person_model.cep {
+                    person_model.address = primary.resolved.clone();
+                }
+            }
+            let annotations = annotations_by_person
+                .remove(&person_model.id)
+                .unwrap_or_default();
+            Person::from_model(
+                person_model,
+                addresses.into_iter().map(PersonAddress::from).collect(),
+                annotations,
+            )
+        })
+        .collect();
+    Ok(persons)
+}
+
+async fn resolve_unverified(
+    addresses: Vec<AddressModel>,
+    state: &ApplicationState,
+) -> Vec<AddressModel> {
+    stream::iter(addresses)
+        .map(|mut address| async move {
+            if address.resolved.is_verified() {
+                return address;
+            }
+            if let Ok(resolved) = state.cep_service.get_address(address.cep).await {
+                address.resolved = ResolvedAddress::from(resolved);
+            }
+            address
+        })
+        .buffered(RESOLVE_CONCURRENCY)
+        .collect()
+        .await
+}
@@ -1,20 +1,42 @@
+pub mod commands;
+pub mod jobs;
+pub mod loaders;
 pub mod messages;
 pub mod middlewares;
 pub mod objects;
+pub mod pagination;
+pub mod patch;
+pub mod preconditions;
 pub mod routers;
 pub mod state;
 
 use axum::{middleware, Json, Router};
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use commands::{
+    import_ceps::{import_ceps, ImportFormat},
+    reindex_phonetics::reindex_phonetics,
+};
 use dotenv::dotenv;
 use hyper::{
-    header::{ACCEPT, AUTHORIZATION},
+    header::{
+        ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+        LAST_MODIFIED, LINK,
+    },
     Method, StatusCode,
 };
 use messages::GenericMessage;
-use middlewares::authorization::auth;
-use routers::{login, persons, users};
-use std::{env, error::Error, net::SocketAddr, path::Path};
+use middlewares::{
+    authorization::auth,
+    http_cache::{http_cache, CachePolicies},
+    request_id::{request_id, REQUEST_ID_HEADER},
+};
+use routers::{addresses, annotations, audit, ceps, login, persons, users};
+use std::{
+    env,
+    error::Error,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
 use tokio::{
     net::{TcpListener, UnixListener},
     signal::ctrl_c,
@@ -31,6 +53,23 @@ pub enum ServiceMode {
 struct Opts {
     #[arg(short = 'm', long = "mode", value_enum, default_value = "address")]
     mode: ServiceMode,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Import or update the offline CEP dataset.
+    ImportCeps {
+        /// CSV file, or directory holding the e-DNE files.
+        path: PathBuf,
+        #[arg(short = 'f', long = "format", value_enum, default_value = "csv")]
+        format: ImportFormat,
+        #[arg(short = 'd', long = "delimiter", default_value = ",")]
+        delimiter: char,
+    },
+    /// Recompute the phonetic name keys used to detect duplicate persons.
+    ReindexPhonetics,
 }
 
 async fn socket_serve(rt: Router) -> std::io::Result<()> {
@@ -84,7 +123,19 @@ async fn address_serve(rt: Router) -> std::io::Result<()> {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok();
+    let opts: Opts = Opts::parse();
+    match opts.command {
+        Some(Command::ImportCeps {
+            path,
+            format,
+            delimiter,
+        }) => return import_ceps(path, format, delimiter).await,
+        Some(Command::ReindexPhonetics) => return reindex_phonetics().await,
+        None => {}
+    }
+
     let app_state = state::ApplicationState::new().await;
+    let refresh_handle = jobs::address_refresh::spawn(app_state.clone());
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods([
@@ -92,23 +143,46 @@ async fn main() -> Result<(), Box<dyn Error>> {
             Method::OPTIONS,
             Method::POST,
             Method::PUT,
+            Method::PATCH,
             Method::DELETE,
         ])
-        .allow_headers([AUTHORIZATION, ACCEPT]);
+        .allow_headers([
+            AUTHORIZATION,
+            ACCEPT,
+            CONTENT_TYPE,
+            IF_MATCH,
+            IF_NONE_MATCH,
+            IF_MODIFIED_SINCE,
+            REQUEST_ID_HEADER,
+        ])
+        .expose_headers([ETAG, LAST_MODIFIED, LINK, REQUEST_ID_HEADER]);
+    // Reads revalidate by default, so polling clients get cheap 304s once nothing changed.
+    let cache_policies = CachePolicies::new("private, no-cache")
+        .route("/api/ceps/{cep}", "private, max-age=86400")
+        .route("/api/ceps/cache", "no-store")
+        .route("/api/audit", "no-store");
     let api = Router::new()
         .merge(login::get_router())
         .merge(users::get_router().layer(middleware::from_fn_with_state(app_state.clone(), auth)))
+        .merge(persons::get_router().layer(middleware::from_fn_with_state(app_state.clone(), auth)))
         .merge(
-            persons::get_router().layer(middleware::from_fn_with_state(app_state.clone(), auth)),
-        );
+            addresses::get_router().layer(middleware::from_fn_with_state(app_state.clone(), auth)),
+        )
+        .merge(
+            annotations::get_router()
+                .layer(middleware::from_fn_with_state(app_state.clone(), auth)),
+        )
+        .merge(ceps::get_router().layer(middleware::from_fn_with_state(app_state.clone(), auth)))
+        .merge(audit::get_router().layer(middleware::from_fn_with_state(app_state.clone(), auth)))
+        .layer(middleware::from_fn_with_state(cache_policies, http_cache));
 
     let app = Router::new()
         .nest("/api", api)
         .with_state(app_state.clone())
+        .layer(middleware::from_fn(request_id))
         .layer(cors)
         .fallback(deal_with_it);
 
-    let opts: Opts = Opts::parse();
     let server_handle = tokio::spawn(async move {
         match opts.mode {
             ServiceMode::Socket => {
@@ -122,6 +196,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     ctrl_c().await.unwrap();
     server_handle.abort();
+    refresh_handle.abort();
     Ok(())
 }
 
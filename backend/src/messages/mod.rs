@@ -1,3 +1,5 @@
+use axum::{http::StatusCode, Json};
+use cep_service::error::CepServiceError;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,3 +22,27 @@ impl Default for GenericMessage {
         }
     }
 }
+
+/// Maps a CEP lookup failure to the HTTP status that best describes it.
+pub fn cep_error_status(error: &CepServiceError) -> StatusCode {
+    match error {
+        CepServiceError::InvalidInput(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        CepServiceError::NotFound => StatusCode::NOT_FOUND,
+        CepServiceError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        CepServiceError::Transport { .. } | CepServiceError::Decode { .. } => {
+            StatusCode::BAD_GATEWAY
+        }
+        CepServiceError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+        CepServiceError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+impl From<CepServiceError> for GenericMessage {
+    fn from(error: CepServiceError) -> Self {
+        Self::new(cep_error_status(&error).as_u16(), error.to_string())
+    }
+}
+
+pub fn cep_error_response(error: CepServiceError) -> (StatusCode, Json<GenericMessage>) {
+    (cep_error_status(&error), Json(GenericMessage::from(error)))
+}
@@ -1 +1,3 @@
 pub mod authorization;
+pub mod http_cache;
+pub mod request_id;
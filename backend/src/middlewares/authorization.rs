@@ -7,7 +7,10 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use database::{models::user::UserModel, traits::token::Token};
+use database::{
+    models::{audit_event::AuditContext, user::UserModel},
+    traits::token::Token,
+};
 use uuid::Uuid;
 
 use crate::{messages::GenericMessage, state::ApplicationState};
@@ -47,14 +50,14 @@ pub async fn auth(
     };
     if state.user_cached(&auth_header) {
         let user = state.get_user_cache(&auth_header).unwrap();
-        req.extensions_mut().insert(user);
+        insert_user(&mut req, user);
         return Ok(next.run(req).await);
     }
     let user = UserModel::get_by_uuid(user_uuid, &state.database_connection).await;
     match user {
         Ok(user) => {
             state.insert_user_cache(&auth_header, &user);
-            req.extensions_mut().insert(user);
+            insert_user(&mut req, user);
             Ok(next.run(req).await)
         }
         Err(_) => Err((
@@ -66,3 +69,17 @@ pub async fn auth(
         )),
     }
 }
+
+/// Hands the user to the handlers and names them as the actor of any audited change.
+fn insert_user(req: &mut Request, user: UserModel) {
+    let audit = req
+        .extensions()
+        .get::<AuditContext>()
+        .cloned()
+        .unwrap_or_default();
+    req.extensions_mut().insert(AuditContext {
+        actor_id: Some(user.id),
+        ..audit
+    });
+    req.extensions_mut().insert(user);
+}
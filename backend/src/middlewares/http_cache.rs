@@ -0,0 +1,130 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{MatchedPath, Request, State},
+    http::{
+        header::{
+            CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+            LAST_MODIFIED,
+        },
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+/// Hex digits of the body digest kept in entity tags.
+const DIGEST_LENGTH: usize = 32;
+
+/// Largest body buffered to compute its tag; bigger or streamed bodies go out untagged.
+const MAX_TAGGED_BODY: u64 = 1024 * 1024;
+
+/// `Cache-Control` value of each route, keyed by its matched path (`/api/persons/{id}`).
+#[derive(Debug, Clone)]
+pub struct CachePolicies {
+    default: HeaderValue,
+    routes: Arc<HashMap<String, HeaderValue>>,
+}
+
+impl CachePolicies {
+    pub fn new(default: &'static str) -> Self {
+        Self {
+            default: HeaderValue::from_static(default),
+            routes: Arc::new(HashMap::new()),
+        }
+    }
+
+    pub fn route(mut self, path: &str, policy: &'static str) -> Self {
+        Arc::make_mut(&mut self.routes).insert(path.to_string(), HeaderValue::from_static(policy));
+        self
+    }
+
+    fn policy(&self, path: Option<&str>) -> HeaderValue {
+        path.and_then(|path| self.routes.get(path))
+            .unwrap_or(&self.default)
+            .clone()
+    }
+}
+
+/// Conditional GET for successful reads: sets the route's `Cache-Control`, tags the body with a
+/// strong ETag and answers `304 Not Modified` to a matching `If-None-Match`, or to an
+/// `If-Modified-Since` no older than the handler's `Last-Modified`. A handler's own ETag (a row
+/// version) is kept as the tag's prefix so it still works with `If-Match`. Bodies over
+/// `MAX_TAGGED_BODY` are neither tagged nor answered with 304.
+pub async fn http_cache(
+    State(policies): State<CachePolicies>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return next.run(req).await;
+    }
+    let policy = policies.policy(
+        req.extensions()
+            .get::<MatchedPath>()
+            .map(MatchedPath::as_str),
+    );
+    let if_none_match = req.headers().get(IF_NONE_MATCH).cloned();
+    let if_modified_since = req.headers().get(IF_MODIFIED_SINCE).cloned();
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    parts.headers.entry(CACHE_CONTROL).or_insert(policy);
+    let size = match body.size_hint().exact() {
+        Some(size) if size <= MAX_TAGGED_BODY => size as usize,
+        _ => return Response::from_parts(parts, body),
+    };
+    let bytes = match to_bytes(body, size).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let digest = format!("{:x}", Sha256::digest(&bytes));
+    let digest = &digest[..DIGEST_LENGTH];
+    let etag = match parts
+        .headers
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|tag| tag.strip_prefix('"')?.strip_suffix('"'))
+    {
+        Some(version) => format!("\"{}-{}\"", version, digest),
+        None => format!("\"{}\"", digest),
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(ETAG, value);
+    }
+
+    // If-None-Match takes precedence over If-Modified-Since (RFC 9110, section 13.2.2).
+    let not_modified = match if_none_match {
+        Some(tags) => none_match(&tags, &etag),
+        None => if_modified_since.is_some_and(|since| not_modified_since(&since, &parts.headers)),
+    };
+    if not_modified {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(CONTENT_LENGTH);
+        parts.headers.remove(CONTENT_TYPE);
+        return Response::from_parts(parts, Body::empty());
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Weak comparison of `If-None-Match` against the current tag.
+fn none_match(tags: &HeaderValue, etag: &str) -> bool {
+    tags.to_str()
+        .unwrap_or_default()
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+fn not_modified_since(since: &HeaderValue, headers: &HeaderMap) -> bool {
+    let parse = |value: &HeaderValue| httpdate::parse_http_date(value.to_str().ok()?).ok();
+    match (parse(since), headers.get(LAST_MODIFIED).and_then(parse)) {
+        (Some(since), Some(modified)) => modified <= since,
+        _ => false,
+    }
+}
@@ -1,3 +1,4 @@
-pub mod person;
 pub mod address;
 pub mod annotation;
+pub mod cep;
+pub mod person;
@@ -1,4 +1,10 @@
-use serde::{Deserialize,Serialize};
+use cep_service::{responses::cep::CepResponse, structs::cep::Cep};
+use chrono::{DateTime, Utc};
+use database::models::{
+    address::{AddressModel, AddressType},
+    person::ResolvedAddress,
+};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Address {
@@ -12,3 +18,68 @@ pub struct Address {
     pub ddd: String,
     pub siafi: String,
 }
+
+impl From<CepResponse> for Address {
+    fn from(address: CepResponse) -> Self {
+        Self {
+            logradouro: address.logradouro,
+            complemento: address.complemento,
+            bairro: address.bairro,
+            localidade: address.localidade,
+            uf: address.uf,
+            ibge: address.ibge,
+            gia: address.gia,
+            ddd: address.ddd,
+            siafi: address.siafi,
+        }
+    }
+}
+
+impl From<ResolvedAddress> for Address {
+    fn from(address: ResolvedAddress) -> Self {
+        Self {
+            logradouro: address.logradouro,
+            complemento: address.complemento,
+            bairro: address.bairro,
+            localidade: address.localidade,
+            uf: address.uf,
+            ibge: address.ibge,
+            gia: address.gia,
+            ddd: address.ddd,
+            siafi: address.siafi,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PersonAddress {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub address_type: AddressType,
+    pub cep: Cep,
+    pub number: String,
+    pub complement: String,
+    pub is_primary: bool,
+    pub address: Option<Address>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<AddressModel> for PersonAddress {
+    fn from(address: AddressModel) -> Self {
+        Self {
+            id: address.id,
+            address_type: address.address_type,
+            cep: address.cep,
+            number: address.number,
+            complement: address.complement,
+            is_primary: address.is_primary,
+            address: match address.resolved.is_verified() {
+                true => Some(Address::from(address.resolved)),
+                false => None,
+            },
+            created_at: address.created_at,
+            updated_at: address.updated_at,
+        }
+    }
+}
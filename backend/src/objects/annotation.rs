@@ -1,10 +1,11 @@
-use database::models::annotation::AnnotationModel;
-use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use database::models::{annotation::AnnotationModel, annotation_revision::AnnotationRevisionModel};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Annotation {
     pub id: u64,
+    pub author_id: Option<u64>,
     pub title: String,
     pub description: String,
     pub created_at: DateTime<Utc>,
@@ -15,6 +16,7 @@ impl From<AnnotationModel> for Annotation {
     fn from(annotation: AnnotationModel) -> Self {
         Self {
             id: annotation.id,
+            author_id: annotation.author_id,
             title: annotation.title,
             description: annotation.description,
             created_at: annotation.created_at,
@@ -22,3 +24,24 @@ impl From<AnnotationModel> for Annotation {
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnnotationRevision {
+    pub id: u64,
+    pub editor_id: Option<u64>,
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AnnotationRevisionModel> for AnnotationRevision {
+    fn from(revision: AnnotationRevisionModel) -> Self {
+        Self {
+            id: revision.id,
+            editor_id: revision.editor_id,
+            title: revision.title,
+            description: revision.description,
+            created_at: revision.created_at,
+        }
+    }
+}
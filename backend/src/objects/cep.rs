@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{messages::GenericMessage, objects::address::Address};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CepBatchRequest {
+    pub ceps: Vec<String>,
+}
+
+/// Outcome of resolving one CEP of a batch: either `address` or `error` is set.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CepLookup {
+    pub cep: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<GenericMessage>,
+}
@@ -1,7 +1,12 @@
+use cep_service::structs::cep::Cep;
+use chrono::{DateTime, NaiveDate, Utc};
+use database::{duplicates::DuplicateScore, models::person::PersonModel};
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
 
-use crate::objects::{address::Address, annotation::Annotation};
+use crate::objects::{
+    address::{Address, PersonAddress},
+    annotation::Annotation,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Person {
@@ -9,9 +14,82 @@ pub struct Person {
     pub name: String,
     pub mothers_name: String,
     pub fathers_name: String,
-    pub cep: String,
+    pub cep: Option<Cep>,
     pub address: Option<Address>,
+    pub addresses: Vec<PersonAddress>,
     pub annotations: Vec<Annotation>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+impl Person {
+    /// Builds the response from the stored person; `address` mirrors the primary address and is
+    /// only exposed once verified.
+    pub fn from_model(
+        person_model: PersonModel,
+        addresses: Vec<PersonAddress>,
+        annotations: Vec<Annotation>,
+    ) -> Self {
+        Self {
+            id: person_model.id,
+            name: person_model.name,
+            mothers_name: person_model.mothers_name,
+            fathers_name: person_model.fathers_name,
+            cep: person_model.cep,
+            address: match person_model.address.is_verified() {
+                true => Some(Address::from(person_model.address)),
+                false => None,
+            },
+            addresses,
+            annotations,
+            created_at: person_model.created_at,
+            updated_at: person_model.updated_at,
+        }
+    }
+
+    /// Latest change to the person or to the addresses and annotations shown with them.
+    pub fn last_modified(&self) -> DateTime<Utc> {
+        let addresses = self
+            .addresses
+            .iter()
+            .map(|address| address.updated_at.unwrap_or(address.created_at));
+        let annotations = self
+            .annotations
+            .iter()
+            .map(|annotation| annotation.updated_at.unwrap_or(annotation.created_at));
+        addresses
+            .chain(annotations)
+            .fold(self.updated_at.unwrap_or(self.created_at), DateTime::max)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PersonMatch {
+    pub relevance: f64,
+    #[serde(flatten)]
+    pub person: Person,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PersonDuplicate {
+    pub id: u64,
+    pub name: String,
+    pub mothers_name: String,
+    pub birth_date: NaiveDate,
+    pub cep: Option<Cep>,
+    #[serde(flatten)]
+    pub score: DuplicateScore,
+}
+
+impl From<(PersonModel, DuplicateScore)> for PersonDuplicate {
+    fn from((person, score): (PersonModel, DuplicateScore)) -> Self {
+        Self {
+            id: person.id,
+            name: person.name,
+            mothers_name: person.mothers_name,
+            birth_date: person.birth_date,
+            cep: person.cep,
+            score,
+        }
+    }
+}
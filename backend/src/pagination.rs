@@ -0,0 +1,51 @@
+use axum::http::{header::LINK, HeaderMap, HeaderValue, Uri};
+use database::pagination::Page;
+
+/// Builds the `Link` header (RFC 8288) for a page, keeping every other query parameter of the
+/// request. Offset pages get `first`, `prev`, `next` and `last`; cursor pages get `first` and
+/// `next`.
+pub fn link_headers<T>(uri: &Uri, page: &Page<T>) -> HeaderMap {
+    let query: Vec<(String, String)> =
+        serde_urlencoded::from_str(uri.query().unwrap_or_default()).unwrap_or_default();
+    let link = |key: Option<(&str, String)>, rel: &str| {
+        let mut pairs: Vec<(String, String)> = query
+            .iter()
+            .filter(|(name, _)| name != "cursor" && name != "offset")
+            .cloned()
+            .collect();
+        if let Some((name, value)) = key {
+            pairs.push((name.to_string(), value));
+        }
+        let query = serde_urlencoded::to_string(pairs).unwrap_or_default();
+        format!("<{}?{}>; rel=\"{}\"", uri.path(), query, rel)
+    };
+
+    let limit = page.limit as u64;
+    let mut links = vec![link(None, "first")];
+    match page.offset {
+        Some(offset) => {
+            if offset > 0 {
+                let previous = offset.saturating_sub(limit);
+                links.push(link(Some(("offset", previous.to_string())), "prev"));
+            }
+            if offset + limit < page.total {
+                links.push(link(Some(("offset", (offset + limit).to_string())), "next"));
+            }
+            if page.total > 0 {
+                let last = (page.total - 1) / limit * limit;
+                links.push(link(Some(("offset", last.to_string())), "last"));
+            }
+        }
+        None => {
+            if let Some(cursor) = &page.next_cursor {
+                links.push(link(Some(("cursor", cursor.clone())), "next"));
+            }
+        }
+    }
+
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+        headers.insert(LINK, value);
+    }
+    headers
+}
@@ -0,0 +1,23 @@
+use serde_json::Value;
+
+/// Applies a JSON Merge Patch (RFC 7396): objects are merged key by key, `null` removes a key
+/// and any other value replaces the target.
+pub fn merge_patch(target: &mut Value, patch: Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(Default::default());
+            }
+            let target = target.as_object_mut().unwrap();
+            for (key, value) in patch {
+                match value {
+                    Value::Null => {
+                        target.remove(&key);
+                    }
+                    value => merge_patch(target.entry(key).or_insert(Value::Null), value),
+                }
+            }
+        }
+        patch => *target = patch,
+    }
+}
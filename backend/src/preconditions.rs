@@ -0,0 +1,88 @@
+use std::{error::Error, time::SystemTime};
+
+use axum::{
+    http::{
+        header::{ETAG, IF_MATCH, LAST_MODIFIED},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    Json,
+};
+use chrono::{DateTime, Utc};
+
+use crate::messages::GenericMessage;
+
+/// Strong entity tag of a row at `version`.
+pub fn etag(version: u32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// `ETag` and `Last-Modified` of a record. The cache middleware appends a digest of the body
+/// to the tag.
+pub fn validators(version: u32, last_modified: DateTime<Utc>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&etag(version)) {
+        headers.insert(ETAG, value);
+    }
+    let last_modified = httpdate::fmt_http_date(SystemTime::from(last_modified));
+    if let Ok(value) = HeaderValue::from_str(&last_modified) {
+        headers.insert(LAST_MODIFIED, value);
+    }
+    headers
+}
+
+pub fn precondition_failed() -> (StatusCode, Json<GenericMessage>) {
+    (
+        StatusCode::PRECONDITION_FAILED,
+        Json(GenericMessage::new(
+            412,
+            "The resource changed since it was read; fetch it again and retry".to_string(),
+        )),
+    )
+}
+
+/// Requires an `If-Match` header (RFC 9110) naming the stored `version` or `*`: missing is
+/// 428, anything else 412. Only the version part of a tag is compared, so tags from both
+/// reads and writes work. Weak tags never match.
+pub fn check_if_match(
+    headers: &HeaderMap,
+    version: u32,
+) -> Result<(), (StatusCode, Json<GenericMessage>)> {
+    let Some(if_match) = headers.get(IF_MATCH) else {
+        return Err((
+            StatusCode::PRECONDITION_REQUIRED,
+            Json(GenericMessage::new(
+                428,
+                "An If-Match header with the resource's ETag is required".to_string(),
+            )),
+        ));
+    };
+    let current = version.to_string();
+    let matches = if_match
+        .to_str()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag_version(tag) == Some(current.as_str()));
+    match matches {
+        true => Ok(()),
+        false => Err(precondition_failed()),
+    }
+}
+
+/// Version part of a strong tag, either `"3"` or `"3-<digest>"`.
+fn tag_version(tag: &str) -> Option<&str> {
+    let tag = tag.strip_prefix('"')?.strip_suffix('"')?;
+    tag.split('-').next()
+}
+
+/// Maps a failed versioned write. `RowNotFound` means another write got in between the
+/// `If-Match` check and this one.
+pub fn write_error(error: &(dyn Error + 'static)) -> (StatusCode, Json<GenericMessage>) {
+    match error.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::RowNotFound) => precondition_failed(),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        ),
+    }
+}
@@ -1,3 +1,7 @@
+pub mod addresses;
+pub mod annotations;
+pub mod audit;
+pub mod ceps;
 pub mod login;
-pub mod users;
 pub mod persons;
+pub mod users;
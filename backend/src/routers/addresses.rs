@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    routing::get,
+    Extension, Json, Router,
+};
+use cep_service::{error::CepServiceError, structs::cep::Cep};
+use database::{
+    models::{
+        address::{AddressModel, NewAddressModel},
+        audit_event::AuditContext,
+        person::ResolvedAddress,
+    },
+    traits::audit::Audit,
+};
+use hyper::StatusCode;
+
+use crate::{
+    messages::{cep_error_response, GenericMessage},
+    objects::address::PersonAddress,
+    preconditions::{check_if_match, validators, write_error},
+    routers::persons::find_person,
+    state::ApplicationState,
+};
+
+pub fn get_router() -> Router<ApplicationState> {
+    Router::new()
+        .route(
+            "/persons/{id}/addresses",
+            get(list_addresses).post(create_address),
+        )
+        .route(
+            "/persons/{id}/addresses/{address_id}",
+            get(get_address).put(update_address).delete(delete_address),
+        )
+}
+
+/// Resolves a CEP for storage, turning an unknown CEP into a validation error.
+pub async fn resolve_address(
+    state: &ApplicationState,
+    cep: Cep,
+) -> Result<ResolvedAddress, (StatusCode, Json<GenericMessage>)> {
+    match state.cep_service.get_address(cep).await {
+        Ok(address) => Ok(ResolvedAddress::from(address)),
+        Err(CepServiceError::NotFound) => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(422, "CEP not found".to_string())),
+        )),
+        Err(error) => Err(cep_error_response(error)),
+    }
+}
+
+async fn find_address(
+    state: &ApplicationState,
+    id: u64,
+    address_id: u64,
+) -> Result<AddressModel, (StatusCode, Json<GenericMessage>)> {
+    AddressModel::get_by_person(id, address_id, &state.database_connection)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(404, "Address not found".to_string())),
+            )
+        })
+}
+
+pub async fn list_addresses(
+    State(state): State<ApplicationState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Vec<PersonAddress>>, (StatusCode, Json<GenericMessage>)> {
+    find_person(id, &state).await?;
+    match AddressModel::list_by_person(id, &state.database_connection).await {
+        Ok(addresses) => Ok(Json(
+            addresses.into_iter().map(PersonAddress::from).collect(),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn get_address(
+    State(state): State<ApplicationState>,
+    Path((id, address_id)): Path<(u64, u64)>,
+) -> Result<(HeaderMap, Json<PersonAddress>), (StatusCode, Json<GenericMessage>)> {
+    let address = find_address(&state, id, address_id).await?;
+    Ok(with_validators(address))
+}
+
+fn with_validators(address: AddressModel) -> (HeaderMap, Json<PersonAddress>) {
+    (
+        validators(
+            address.version,
+            address.updated_at.unwrap_or(address.created_at),
+        ),
+        Json(PersonAddress::from(address)),
+    )
+}
+
+pub async fn create_address(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    Json(address): Json<NewAddressModel>,
+) -> Result<(StatusCode, HeaderMap, Json<PersonAddress>), (StatusCode, Json<GenericMessage>)> {
+    find_person(id, &state).await?;
+    let resolved = resolve_address(&state, address.cep).await?;
+    let address = AddressModel::new(id, address, resolved);
+    match address
+        .insert_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(address) => {
+            let (headers, address) = with_validators(address);
+            Ok((StatusCode::CREATED, headers, address))
+        }
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn update_address(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, address_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+    Json(changes): Json<NewAddressModel>,
+) -> Result<(HeaderMap, Json<PersonAddress>), (StatusCode, Json<GenericMessage>)> {
+    let current = find_address(&state, id, address_id).await?;
+    check_if_match(&headers, current.version)?;
+    let resolved = match changes.cep == current.cep && current.resolved.is_verified() {
+        true => current.resolved,
+        false => resolve_address(&state, changes.cep).await?,
+    };
+    let address = AddressModel {
+        id: current.id,
+        is_primary: current.is_primary || changes.is_primary,
+        version: current.version,
+        created_at: current.created_at,
+        ..AddressModel::new(id, changes, resolved)
+    };
+    match address
+        .update_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(address) => Ok(with_validators(address)),
+        Err(error) => Err(write_error(&error)),
+    }
+}
+
+pub async fn delete_address(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, address_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+) -> Result<Json<GenericMessage>, (StatusCode, Json<GenericMessage>)> {
+    let address = find_address(&state, id, address_id).await?;
+    check_if_match(&headers, address.version)?;
+    match address
+        .delete_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(_) => Ok(Json(GenericMessage::new(
+            200,
+            "Address deleted successfully".to_string(),
+        ))),
+        Err(error) => Err(write_error(&*error)),
+    }
+}
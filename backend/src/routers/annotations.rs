@@ -0,0 +1,286 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use database::{
+    models::{
+        annotation::{AnnotationModel, NewAnnotationModel},
+        annotation_revision::AnnotationRevisionModel,
+        audit_event::AuditContext,
+        user::UserModel,
+    },
+    traits::audit::Audit,
+};
+use hyper::StatusCode;
+use serde_json::Value;
+
+use crate::{
+    messages::GenericMessage,
+    objects::annotation::{Annotation, AnnotationRevision},
+    patch::merge_patch,
+    preconditions::{check_if_match, validators, write_error},
+    routers::persons::{find_person, require_admin},
+    state::ApplicationState,
+};
+
+pub fn get_router() -> Router<ApplicationState> {
+    Router::new()
+        .route(
+            "/persons/{id}/annotations",
+            get(list_annotations).post(create_annotation),
+        )
+        .route(
+            "/persons/{id}/annotations/{annotation_id}",
+            get(get_annotation)
+                .put(replace_annotation)
+                .patch(patch_annotation)
+                .delete(delete_annotation),
+        )
+        .route(
+            "/persons/{id}/annotations/{annotation_id}/revisions",
+            get(list_revisions),
+        )
+        .route(
+            "/persons/{id}/annotations/{annotation_id}/restore",
+            post(restore_annotation),
+        )
+}
+
+async fn find_annotation(
+    state: &ApplicationState,
+    id: u64,
+    annotation_id: u64,
+) -> Result<AnnotationModel, (StatusCode, Json<GenericMessage>)> {
+    AnnotationModel::get_by_person(id, annotation_id, &state.database_connection)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(404, "Annotation not found".to_string())),
+            )
+        })
+}
+
+async fn save_annotation(
+    state: &ApplicationState,
+    audit: &AuditContext,
+    annotation: AnnotationModel,
+    changes: NewAnnotationModel,
+) -> Result<(HeaderMap, Json<Annotation>), (StatusCode, Json<GenericMessage>)> {
+    match annotation
+        .with_changes(changes)
+        .update_audited(audit, &state.database_connection)
+        .await
+    {
+        Ok(annotation) => Ok((
+            validators(
+                annotation.version,
+                annotation.updated_at.unwrap_or(annotation.created_at),
+            ),
+            Json(Annotation::from(annotation)),
+        )),
+        Err(error) => Err(write_error(&error)),
+    }
+}
+
+pub async fn list_annotations(
+    State(state): State<ApplicationState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Vec<Annotation>>, (StatusCode, Json<GenericMessage>)> {
+    find_person(id, &state).await?;
+    match AnnotationModel::list_by_person(id, &state.database_connection).await {
+        Ok(annotations) => Ok(Json(
+            annotations.into_iter().map(Annotation::from).collect(),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn get_annotation(
+    State(state): State<ApplicationState>,
+    Path((id, annotation_id)): Path<(u64, u64)>,
+) -> Result<(HeaderMap, Json<Annotation>), (StatusCode, Json<GenericMessage>)> {
+    let annotation = find_annotation(&state, id, annotation_id).await?;
+    Ok((
+        validators(
+            annotation.version,
+            annotation.updated_at.unwrap_or(annotation.created_at),
+        ),
+        Json(Annotation::from(annotation)),
+    ))
+}
+
+pub async fn create_annotation(
+    State(state): State<ApplicationState>,
+    Extension(user): Extension<UserModel>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    Json(annotation): Json<NewAnnotationModel>,
+) -> Result<(StatusCode, HeaderMap, Json<Annotation>), (StatusCode, Json<GenericMessage>)> {
+    find_person(id, &state).await?;
+    match AnnotationModel::new(id, user.id, annotation)
+        .insert_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(annotation) => Ok((
+            StatusCode::CREATED,
+            validators(
+                annotation.version,
+                annotation.updated_at.unwrap_or(annotation.created_at),
+            ),
+            Json(Annotation::from(annotation)),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn replace_annotation(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, annotation_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+    Json(changes): Json<NewAnnotationModel>,
+) -> Result<(HeaderMap, Json<Annotation>), (StatusCode, Json<GenericMessage>)> {
+    let annotation = find_annotation(&state, id, annotation_id).await?;
+    check_if_match(&headers, annotation.version)?;
+    save_annotation(&state, &audit, annotation, changes).await
+}
+
+/// Applies a JSON Merge Patch on top of the annotation's title and description.
+pub async fn patch_annotation(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, annotation_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+    Json(patch): Json<Value>,
+) -> Result<(HeaderMap, Json<Annotation>), (StatusCode, Json<GenericMessage>)> {
+    let annotation = find_annotation(&state, id, annotation_id).await?;
+    check_if_match(&headers, annotation.version)?;
+    let mut document = match serde_json::to_value(NewAnnotationModel::from(&annotation)) {
+        Ok(document) => document,
+        Err(error) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(GenericMessage::new(500, error.to_string())),
+            ))
+        }
+    };
+    merge_patch(&mut document, patch);
+    let changes = match serde_json::from_value::<NewAnnotationModel>(document) {
+        Ok(changes) => changes,
+        Err(error) => {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(GenericMessage::new(422, error.to_string())),
+            ))
+        }
+    };
+    save_annotation(&state, &audit, annotation, changes).await
+}
+
+pub async fn list_revisions(
+    State(state): State<ApplicationState>,
+    Path((id, annotation_id)): Path<(u64, u64)>,
+) -> Result<Json<Vec<AnnotationRevision>>, (StatusCode, Json<GenericMessage>)> {
+    let annotation = find_annotation(&state, id, annotation_id).await?;
+    match AnnotationRevisionModel::list_by_annotation(annotation.id, &state.database_connection)
+        .await
+    {
+        Ok(revisions) => Ok(Json(
+            revisions
+                .into_iter()
+                .map(AnnotationRevision::from)
+                .collect(),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn delete_annotation(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, annotation_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+) -> Result<Json<GenericMessage>, (StatusCode, Json<GenericMessage>)> {
+    let annotation = find_annotation(&state, id, annotation_id).await?;
+    check_if_match(&headers, annotation.version)?;
+    match annotation
+        .soft_delete_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(_) => Ok(Json(GenericMessage::new(
+            200,
+            "Annotation deleted successfully".to_string(),
+        ))),
+        Err(error) => Err(write_error(&*error)),
+    }
+}
+
+/// Brings back a soft-deleted annotation. Admins only.
+pub async fn restore_annotation(
+    State(state): State<ApplicationState>,
+    Extension(user): Extension<UserModel>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, annotation_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<Annotation>), (StatusCode, Json<GenericMessage>)> {
+    require_admin(&user, "Only administrators can restore annotations")?;
+    find_person(id, &state).await?;
+    let deleted = match AnnotationModel::get_with_deleted(
+        id,
+        annotation_id,
+        &state.database_connection,
+    )
+    .await
+    {
+        Ok(annotation) => annotation,
+        Err(_) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(
+                    404,
+                    "Deleted annotation not found".to_string(),
+                )),
+            ))
+        }
+    };
+    check_if_match(&headers, deleted.version)?;
+    match AnnotationModel::restore_audited(
+        annotation_id,
+        deleted.version,
+        &audit,
+        &state.database_connection,
+    )
+    .await
+    {
+        Ok(Some(annotation)) => Ok((
+            validators(
+                annotation.version,
+                annotation.updated_at.unwrap_or(annotation.created_at),
+            ),
+            Json(Annotation::from(annotation)),
+        )),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(GenericMessage::new(
+                404,
+                "Deleted annotation not found".to_string(),
+            )),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
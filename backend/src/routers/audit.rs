@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Extension, OriginalUri, Query, State},
+    http::HeaderMap,
+    routing::get,
+    Json, Router,
+};
+use database::{
+    models::{
+        audit_event::{AuditEventModel, AuditFilter},
+        user::UserModel,
+    },
+    pagination::{Page, PageRequest},
+    traits::paginate::Paginate,
+};
+use hyper::StatusCode;
+
+use crate::{messages::GenericMessage, pagination::link_headers, state::ApplicationState};
+
+pub fn get_router() -> Router<ApplicationState> {
+    Router::new().route("/audit", get(list_audit_events))
+}
+
+/// Audit events, filtered e.g. by `entity=person&id=42`. Admins only.
+pub async fn list_audit_events(
+    State(state): State<ApplicationState>,
+    Extension(user): Extension<UserModel>,
+    OriginalUri(uri): OriginalUri,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<AuditFilter>,
+) -> Result<(HeaderMap, Json<Page<AuditEventModel>>), (StatusCode, Json<GenericMessage>)> {
+    if !user.is_admin {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(GenericMessage::new(
+                403,
+                "Only administrators can read the audit log".to_string(),
+            )),
+        ));
+    }
+    if let Err(message) = page.validate(AuditEventModel::SORT_COLUMNS) {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(422, message)),
+        ));
+    }
+    match AuditEventModel::paginate(&page, &filter, &state.database_connection).await {
+        Ok(events) => Ok((link_headers(&uri, &events), Json(events))),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
@@ -0,0 +1,111 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use cep_service::structs::{
+    breaker::ProviderHealth, cep::Cep, memory_cache::CacheStats, search::AddressQuery,
+};
+use futures::{stream, StreamExt};
+
+use crate::{
+    messages::{cep_error_response, GenericMessage},
+    objects::{
+        address::Address,
+        cep::{CepBatchRequest, CepLookup},
+    },
+    state::ApplicationState,
+};
+
+const MAX_BATCH_SIZE: usize = 100;
+const BATCH_CONCURRENCY: usize = 8;
+
+pub fn get_router() -> Router<ApplicationState> {
+    Router::new()
+        .route("/ceps/providers", get(list_providers))
+        .route("/ceps/cache", get(cache_stats))
+        .route("/ceps/batch", post(get_addresses))
+        .route("/ceps/search", get(search_addresses))
+        .route("/ceps/{cep}", get(get_address))
+}
+
+pub async fn list_providers(State(state): State<ApplicationState>) -> Json<Vec<ProviderHealth>> {
+    Json(state.cep_service.health())
+}
+
+pub async fn cache_stats(State(state): State<ApplicationState>) -> Json<CacheStats> {
+    Json(state.cep_service.cache_stats())
+}
+
+pub async fn get_address(
+    State(state): State<ApplicationState>,
+    Path(cep): Path<String>,
+) -> Result<Json<Address>, (StatusCode, Json<GenericMessage>)> {
+    let cep = Cep::try_from(cep).map_err(cep_error_response)?;
+    match state.cep_service.get_address(cep).await {
+        Ok(address) => Ok(Json(Address::from(address))),
+        Err(error) => Err(cep_error_response(error)),
+    }
+}
+
+pub async fn get_addresses(
+    State(state): State<ApplicationState>,
+    Json(request): Json<CepBatchRequest>,
+) -> Result<Json<Vec<CepLookup>>, (StatusCode, Json<GenericMessage>)> {
+    if request.ceps.len() > MAX_BATCH_SIZE {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(GenericMessage::new(
+                413,
+                format!("At most {} CEPs can be resolved at once", MAX_BATCH_SIZE),
+            )),
+        ));
+    }
+
+    let lookups = stream::iter(request.ceps)
+        .map(|cep| {
+            let state = state.clone();
+            async move {
+                let result = match Cep::try_from(&cep) {
+                    Ok(parsed) => state.cep_service.get_address(parsed).await,
+                    Err(error) => Err(error),
+                };
+                match result {
+                    Ok(address) => CepLookup {
+                        cep,
+                        address: Some(Address::from(address)),
+                        error: None,
+                    },
+                    Err(error) => CepLookup {
+                        cep,
+                        address: None,
+                        error: Some(GenericMessage::from(error)),
+                    },
+                }
+            }
+        })
+        .buffered(BATCH_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+    Ok(Json(lookups))
+}
+
+pub async fn search_addresses(
+    State(state): State<ApplicationState>,
+    Query(query): Query<AddressQuery>,
+) -> Result<Json<Vec<CepLookup>>, (StatusCode, Json<GenericMessage>)> {
+    match state.cep_service.search_by_address(&query).await {
+        Ok(candidates) => Ok(Json(
+            candidates
+                .into_iter()
+                .map(|candidate| CepLookup {
+                    cep: candidate.cep.clone(),
+                    address: Some(Address::from(candidate)),
+                    error: None,
+                })
+                .collect(),
+        )),
+        Err(error) => Err(cep_error_response(error)),
+    }
+}
@@ -1,7 +1,10 @@
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{extract::State, routing::post, Extension, Json, Router};
 use database::{
-    models::user::{LoginModel, NewUserModel, UserModel},
-    traits::{login::Login, persist::Persist},
+    models::{
+        audit_event::AuditContext,
+        user::{LoginModel, NewUserModel, UserModel},
+    },
+    traits::{audit::Audit, login::Login},
 };
 use hyper::StatusCode;
 
@@ -28,6 +31,7 @@ async fn login(
 
 async fn create_user(
     State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
     Json(user): Json<NewUserModel>,
 ) -> Result<Json<UserModel>, (StatusCode, String)> {
     let user = match UserModel::try_from(user) {
@@ -35,7 +39,10 @@ async fn create_user(
         Err(error) => return Err((StatusCode::BAD_REQUEST, error.to_string())),
     };
 
-    match user.insert(&state.database_connection).await {
+    match user
+        .insert_audited(&audit, &state.database_connection)
+        .await
+    {
         Ok(user) => Ok(Json(user)),
         Err(error) => Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string())),
     }
@@ -1,90 +1,80 @@
 use crate::{
-    objects::{address::Address, annotation::Annotation, person::Person},
+    loaders::person::load_persons,
+    objects::person::{Person, PersonDuplicate, PersonMatch},
+    pagination::link_headers,
+    patch::merge_patch,
+    preconditions::{check_if_match, validators, write_error},
+    routers::addresses::resolve_address,
     state::ApplicationState,
 };
 use axum::{
-    extract::{Path, State},
-    routing::{delete, get, post},
-    Json, Router,
+    extract::{OriginalUri, Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Redirect, Response},
+    routing::{delete, get, patch, post, put},
+    Extension, Json, Router,
 };
-use cep_service::{responses::service::CepServiceResponse, structs::cep::Cep};
 use database::{
-    models::annotation::AnnotationModel,
-    models::person::{NewPersonModel, PersonModel},
+    models::address::{AddressType, NewAddressModel},
+    models::audit_event::AuditContext,
+    models::person::{NewPersonModel, PersonFilter, PersonModel, PersonSearch, UpdatePersonModel},
+    models::person_merge::{MergeRequest, PersonMergeModel},
+    models::person_version::PersonVersionModel,
+    models::user::UserModel,
+    pagination::{Page, PageRequest},
+    traits::audit::Audit,
     traits::database::Database,
-    traits::persist::Persist,
+    traits::paginate::Paginate,
 };
 
+use cep_service::structs::cep::Cep;
+use chrono::{DateTime, Utc};
 use hyper::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
 
 use crate::messages::GenericMessage;
 
 pub fn get_router() -> Router<ApplicationState> {
     Router::new()
         .route("/persons", get(list_persons))
-        .route("/persons/:id", get(get_person))
+        .route("/persons/search", get(search_persons))
+        .route("/persons/{id}", get(get_person))
         .route("/persons", post(create_person))
-        .route("/persons/:id", delete(delete_person))
+        .route("/persons/{id}", put(replace_person))
+        .route("/persons/{id}", patch(patch_person))
+        .route("/persons/{id}", delete(delete_person))
+        .route("/persons/{id}/restore", post(restore_person))
+        .route("/persons/{id}/purge", delete(purge_person))
+        .route("/persons/{id}/duplicates", get(list_duplicates))
+        .route("/persons/{id}/merge", post(merge_person))
+        .route("/persons/{id}/merges", get(list_merges))
+        .route("/persons/{id}/history", get(list_history))
+        .route(
+            "/persons/{id}/history/{version}/revert",
+            post(revert_person),
+        )
 }
 
 pub async fn list_persons(
     State(state): State<ApplicationState>,
-) -> Result<Json<Vec<Person>>, (StatusCode, Json<GenericMessage>)> {
-    match PersonModel::list(&state.database_connection).await {
-        Ok(person_models) => {
-            let mut persons = Vec::new();
-            for person_model in person_models {
-                let cep = match Cep::try_from(person_model.cep.clone()) {
-                    Ok(cep) => cep,
-                    Err(_) => {
-                        continue;
-                    }
-                };
-                let address = state.cep_service.get_address(cep).await;
-
-                persons.push(Person {
-                    id: person_model.id,
-                    name: person_model.name,
-                    mothers_name: person_model.mothers_name,
-                    fathers_name: person_model.fathers_name,
-                    cep: person_model.cep,
-                    address: match address {
-                        CepServiceResponse::CepFound(address) => Some(Address {
-                            logradouro: address.logradouro.clone(),
-                            complemento: address.complemento.clone(),
-                            bairro: address.bairro.clone(),
-                            localidade: address.localidade.clone(),
-                            uf: address.uf.clone(),
-                            ibge: address.ibge.clone(),
-                            gia: address.gia.clone(),
-                            ddd: address.ddd.clone(),
-                            siafi: address.siafi.clone(),
-                        }),
-                        _ => None,
-                    },
-                    annotations: match AnnotationModel::list(
-                        person_model.id,
-                        &state.database_connection,
-                    )
-                    .await
-                    {
-                        Ok(annotations) => annotations
-                            .into_iter()
-                            .map(|annotation_model| Annotation {
-                                id: annotation_model.id,
-                                title: annotation_model.title,
-                                description: annotation_model.description,
-                                created_at: annotation_model.created_at,
-                                updated_at: annotation_model.updated_at,
-                            })
-                            .collect::<Vec<Annotation>>(),
-                        Err(_) => Vec::new(),
-                    },
-                    created_at: person_model.created_at,
-                    updated_at: person_model.updated_at,
-                });
-            }
-            Ok(Json(persons))
+    OriginalUri(uri): OriginalUri,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<PersonFilter>,
+) -> Result<(HeaderMap, Json<Page<Person>>), (StatusCode, Json<GenericMessage>)> {
+    if let Err(message) = page.validate(PersonModel::SORT_COLUMNS) {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(422, message)),
+        ));
+    }
+    match PersonModel::paginate(&page, &filter, &state.database_connection).await {
+        Ok(mut person_models) => {
+            let persons = load_persons(std::mem::take(&mut person_models.items), &state)
+                .await
+                .map_err(load_error)?;
+            let page = person_models.with_items(persons);
+            Ok((link_headers(&uri, &page), Json(page)))
         }
         Err(_) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -96,78 +86,166 @@ pub async fn list_persons(
     }
 }
 
+pub async fn search_persons(
+    State(state): State<ApplicationState>,
+    Query(search): Query<PersonSearch>,
+) -> Result<Json<Vec<PersonMatch>>, (StatusCode, Json<GenericMessage>)> {
+    if search.expression().is_none() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(
+                422,
+                "q must contain a word of at least 3 characters".to_string(),
+            )),
+        ));
+    }
+    match PersonModel::search(&search, &state.database_connection).await {
+        Ok(hits) => {
+            let relevances: Vec<f64> = hits.iter().map(|hit| hit.relevance).collect();
+            let persons = load_persons(hits.into_iter().map(|hit| hit.person).collect(), &state)
+                .await
+                .map_err(load_error)?;
+            Ok(Json(
+                persons
+                    .into_iter()
+                    .zip(relevances)
+                    .map(|(person, relevance)| PersonMatch { relevance, person })
+                    .collect(),
+            ))
+        }
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(
+                500,
+                "Error searching persons".to_string(),
+            )),
+        )),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GetOptions {
+    /// Show the person's own fields as they were at this moment instead.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
 pub async fn get_person(
     State(state): State<ApplicationState>,
     Path(id): Path<u64>,
-) -> Result<Json<Person>, (StatusCode, Json<GenericMessage>)> {
+    Query(options): Query<GetOptions>,
+) -> Result<(HeaderMap, Json<Person>), Response> {
     match PersonModel::get(id, &state.database_connection).await {
-        Ok(person_model) => {
-            let cep = match Cep::try_from(person_model.cep.clone()) {
-                Ok(cep) => cep,
-                Err(_) => {
-                    return Err((
-                        StatusCode::NOT_FOUND,
-                        Json(GenericMessage::new(404, "Person not found".to_string())),
-                    ));
-                }
-            };
-            let address = state.cep_service.get_address(cep).await;
-            let person = Person {
-                id: person_model.id,
-                name: person_model.name,
-                mothers_name: person_model.mothers_name,
-                fathers_name: person_model.fathers_name,
-                cep: person_model.cep,
-                address: match address {
-                    CepServiceResponse::CepFound(address) => Some(Address {
-                        logradouro: address.logradouro.clone(),
-                        complemento: address.complemento.clone(),
-                        bairro: address.bairro.clone(),
-                        localidade: address.localidade.clone(),
-                        uf: address.uf.clone(),
-                        ibge: address.ibge.clone(),
-                        gia: address.gia.clone(),
-                        ddd: address.ddd.clone(),
-                        siafi: address.siafi.clone(),
-                    }),
-                    _ => None,
-                },
-                annotations: match AnnotationModel::list(
-                    person_model.id,
-                    &state.database_connection,
-                )
+        Ok(person_model) => match options.as_of {
+            Some(as_of) => get_person_as_of(person_model, as_of, &state)
                 .await
-                {
-                    Ok(annotations) => annotations
-                        .into_iter()
-                        .map(|annotation_model| Annotation {
-                            id: annotation_model.id,
-                            title: annotation_model.title,
-                            description: annotation_model.description,
-                            created_at: annotation_model.created_at,
-                            updated_at: annotation_model.updated_at,
-                        })
-                        .collect::<Vec<Annotation>>(),
-                    Err(_) => Vec::new(),
-                },
-                created_at: person_model.created_at,
-                updated_at: person_model.updated_at,
-            };
-            Ok(Json(person))
-        }
-        Err(_) => Err((
+                .map_err(IntoResponse::into_response),
+            None => {
+                let version = person_model.version;
+                let person = enrich_person(person_model, &state)
+                    .await
+                    .map_err(IntoResponse::into_response)?;
+                Ok(with_validators(version, person))
+            }
+        },
+        Err(_) => match PersonMergeModel::survivor_of(id, &state.database_connection).await {
+            Ok(Some(survivor_id)) => {
+                Err(Redirect::permanent(&format!("/api/persons/{}", survivor_id)).into_response())
+            }
+            _ => Err((
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(404, "Person not found".to_string())),
+            )
+                .into_response()),
+        },
+    }
+}
+
+/// The version current at `as_of`. Addresses and annotations are not versioned, so they are
+/// left out rather than shown as they are now.
+async fn get_person_as_of(
+    person_model: PersonModel,
+    as_of: DateTime<Utc>,
+    state: &ApplicationState,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    match PersonVersionModel::as_of(person_model.id, as_of, &state.database_connection).await {
+        Ok(Some(version)) => Ok(with_validators(
+            version.version,
+            Person::from_model(version.into_person(person_model), Vec::new(), Vec::new()),
+        )),
+        Ok(None) => Err((
             StatusCode::NOT_FOUND,
-            Json(GenericMessage::new(404, "Person not found".to_string())),
+            Json(GenericMessage::new(
+                404,
+                format!("Person has no version as of {}", as_of),
+            )),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
         )),
     }
 }
 
+pub fn require_admin(
+    user: &UserModel,
+    message: &str,
+) -> Result<(), (StatusCode, Json<GenericMessage>)> {
+    match user.is_admin {
+        true => Ok(()),
+        false => Err((
+            StatusCode::FORBIDDEN,
+            Json(GenericMessage::new(403, message.to_string())),
+        )),
+    }
+}
+
+fn with_validators(version: u32, person: Person) -> (HeaderMap, Json<Person>) {
+    (validators(version, person.last_modified()), Json(person))
+}
+
+async fn enrich_person(
+    person_model: PersonModel,
+    state: &ApplicationState,
+) -> Result<Person, (StatusCode, Json<GenericMessage>)> {
+    let mut persons = load_persons(vec![person_model], state)
+        .await
+        .map_err(load_error)?;
+    Ok(persons.remove(0))
+}
+
+fn load_error(error: sqlx::Error) -> (StatusCode, Json<GenericMessage>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(GenericMessage::new(500, error.to_string())),
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateOptions {
+    #[serde(default)]
+    pub allow_duplicates: bool,
+}
+
 pub async fn create_person(
     State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Query(options): Query<CreateOptions>,
     Json(person): Json<NewPersonModel>,
-) -> Result<Json<PersonModel>, (StatusCode, Json<GenericMessage>)> {
+) -> Result<(HeaderMap, Json<PersonModel>), (StatusCode, Json<GenericMessage>)> {
+    check_uf(person.cep, &person.uf)?;
+    let address = resolve_address(&state, person.cep).await?;
+    let primary_address = NewAddressModel {
+        address_type: AddressType::Residential,
+        cep: person.cep,
+        number: person.number.clone(),
+        complement: person.complement.clone(),
+        is_primary: true,
+    };
     let person = match PersonModel::try_from(person) {
-        Ok(person) => person,
+        Ok(person) => PersonModel {
+            address: address.clone(),
+            ..person
+        },
         Err(error) => {
             return Err((
                 StatusCode::BAD_REQUEST,
@@ -175,8 +253,174 @@ pub async fn create_person(
             ))
         }
     };
-    match person.insert(&state.database_connection).await {
-        Ok(person) => Ok(Json(person)),
+    if !options.allow_duplicates {
+        let duplicates = match person.find_duplicates(&state.database_connection).await {
+            Ok(duplicates) => duplicates,
+            Err(error) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(GenericMessage::new(500, error.to_string())),
+                ))
+            }
+        };
+        let likely: Vec<String> = duplicates
+            .iter()
+            .filter(|(_, score)| score.is_likely())
+            .map(|(duplicate, score)| format!("{} (score {:.2})", duplicate.id, score.score))
+            .collect();
+        if !likely.is_empty() {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(GenericMessage::new(
+                    409,
+                    format!(
+                        "Person looks like a duplicate of {}; retry with allow_duplicates=true to create it anyway",
+                        likely.join(", ")
+                    ),
+                )),
+            ));
+        }
+    }
+    match person
+        .insert_with_address(primary_address, &audit, &state.database_connection)
+        .await
+    {
+        Ok(person) => Ok((
+            validators(
+                person.version,
+                person.updated_at.unwrap_or(person.created_at),
+            ),
+            Json(person),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn replace_person(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+    Json(changes): Json<UpdatePersonModel>,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    let person = find_person(id, &state).await?;
+    check_if_match(&headers, person.version)?;
+    save_person(person, changes, &audit, &state).await
+}
+
+/// Applies a JSON Merge Patch on top of the person's current fields.
+pub async fn patch_person(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+    Json(patch): Json<Value>,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    let person = find_person(id, &state).await?;
+    check_if_match(&headers, person.version)?;
+    let mut document = match serde_json::to_value(UpdatePersonModel::from(&person)) {
+        Ok(document) => document,
+        Err(error) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(GenericMessage::new(500, error.to_string())),
+            ))
+        }
+    };
+    merge_patch(&mut document, patch);
+    let changes = match serde_json::from_value::<UpdatePersonModel>(document) {
+        Ok(changes) => changes,
+        Err(error) => {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(GenericMessage::new(422, error.to_string())),
+            ))
+        }
+    };
+    save_person(person, changes, &audit, &state).await
+}
+
+async fn save_person(
+    person: PersonModel,
+    changes: UpdatePersonModel,
+    audit: &AuditContext,
+    state: &ApplicationState,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    let Some(cep) = changes.cep else {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(422, "A CEP is required".to_string())),
+        ));
+    };
+    check_uf(cep, &changes.uf)?;
+    let cep_changed = person.cep != Some(cep);
+    let address = match cep_changed {
+        true => resolve_address(state, cep).await?,
+        false => person.address.clone(),
+    };
+    let person = person.with_changes(changes, address);
+    let saved = match cep_changed {
+        true => {
+            person
+                .update_with_address(cep, audit, &state.database_connection)
+                .await
+        }
+        false => {
+            person
+                .update_audited(audit, &state.database_connection)
+                .await
+        }
+    };
+    let person = match saved {
+        Ok(person) => person,
+        Err(error) => return Err(write_error(&error)),
+    };
+    let version = person.version;
+    Ok(with_validators(
+        version,
+        enrich_person(person, state).await?,
+    ))
+}
+
+fn check_uf(cep: Cep, uf: &Option<String>) -> Result<(), (StatusCode, Json<GenericMessage>)> {
+    match uf {
+        Some(uf) if !cep.matches_uf(uf) => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(
+                422,
+                format!("CEP {} does not belong to UF {}", cep, uf),
+            )),
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub async fn find_person(
+    id: u64,
+    state: &ApplicationState,
+) -> Result<PersonModel, (StatusCode, Json<GenericMessage>)> {
+    PersonModel::get(id, &state.database_connection)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(404, "Person not found".to_string())),
+            )
+        })
+}
+
+pub async fn list_duplicates(
+    State(state): State<ApplicationState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Vec<PersonDuplicate>>, (StatusCode, Json<GenericMessage>)> {
+    let person = find_person(id, &state).await?;
+    match person.find_duplicates(&state.database_connection).await {
+        Ok(duplicates) => Ok(Json(
+            duplicates.into_iter().map(PersonDuplicate::from).collect(),
+        )),
         Err(error) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(GenericMessage::new(500, error.to_string())),
@@ -184,9 +428,98 @@ pub async fn create_person(
     }
 }
 
+pub async fn merge_person(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+    Json(request): Json<MergeRequest>,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    if request.source_id == id {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(GenericMessage::new(
+                422,
+                "A person cannot be merged into itself".to_string(),
+            )),
+        ));
+    }
+    let target = find_person(id, &state).await?;
+    check_if_match(&headers, target.version)?;
+    find_person(request.source_id, &state).await?;
+    match PersonMergeModel::merge(
+        id,
+        target.version,
+        &request,
+        &audit,
+        &state.database_connection,
+    )
+    .await
+    {
+        Ok(person) => {
+            let version = person.version;
+            Ok(with_validators(
+                version,
+                enrich_person(person, &state).await?,
+            ))
+        }
+        Err(error) => Err(write_error(&error)),
+    }
+}
+
+pub async fn list_merges(
+    State(state): State<ApplicationState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Vec<PersonMergeModel>>, (StatusCode, Json<GenericMessage>)> {
+    find_person(id, &state).await?;
+    match PersonMergeModel::list_by_survivor(id, &state.database_connection).await {
+        Ok(merges) => Ok(Json(merges)),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+pub async fn list_history(
+    State(state): State<ApplicationState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Vec<PersonVersionModel>>, (StatusCode, Json<GenericMessage>)> {
+    find_person(id, &state).await?;
+    match PersonVersionModel::list_by_person(id, &state.database_connection).await {
+        Ok(versions) => Ok(Json(versions)),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+/// Saves the fields of an earlier version as a new update, so the history stays linear.
+pub async fn revert_person(
+    State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
+    Path((id, version)): Path<(u64, u32)>,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    let person = find_person(id, &state).await?;
+    check_if_match(&headers, person.version)?;
+    let version = PersonVersionModel::get_by_person(id, version, &state.database_connection)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(404, "Version not found".to_string())),
+            )
+        })?;
+    save_person(person, UpdatePersonModel::from(&version), &audit, &state).await
+}
+
 pub async fn delete_person(
     State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
     Path(id): Path<u64>,
+    headers: HeaderMap,
 ) -> Result<Json<GenericMessage>, (StatusCode, Json<GenericMessage>)> {
     let person = match PersonModel::get(id, &state.database_connection).await {
         Ok(person) => person,
@@ -197,11 +530,92 @@ pub async fn delete_person(
             ))
         }
     };
-    match person.delete(&state.database_connection).await {
+    check_if_match(&headers, person.version)?;
+    match person
+        .soft_delete_audited(&audit, &state.database_connection)
+        .await
+    {
         Ok(_) => Ok(Json(GenericMessage::new(
             200,
             "Person deleted successfully".to_string(),
         ))),
+        Err(error) => Err(write_error(&*error)),
+    }
+}
+
+/// Brings back a soft-deleted person. Admins only.
+pub async fn restore_person(
+    State(state): State<ApplicationState>,
+    Extension(user): Extension<UserModel>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<Person>), (StatusCode, Json<GenericMessage>)> {
+    require_admin(&user, "Only administrators can restore persons")?;
+    let deleted = match PersonModel::get_with_deleted(id, &state.database_connection).await {
+        Ok(person) => person,
+        Err(_) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(
+                    404,
+                    "Deleted person not found".to_string(),
+                )),
+            ))
+        }
+    };
+    check_if_match(&headers, deleted.version)?;
+    match PersonModel::restore_audited(id, deleted.version, &audit, &state.database_connection)
+        .await
+    {
+        Ok(Some(person)) => {
+            let version = person.version;
+            Ok(with_validators(
+                version,
+                enrich_person(person, &state).await?,
+            ))
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(GenericMessage::new(
+                404,
+                "Deleted person not found".to_string(),
+            )),
+        )),
+        Err(error) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(GenericMessage::new(500, error.to_string())),
+        )),
+    }
+}
+
+/// Removes a person, deleted or not, along with their addresses and annotations. Admins only.
+pub async fn purge_person(
+    State(state): State<ApplicationState>,
+    Extension(user): Extension<UserModel>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+) -> Result<Json<GenericMessage>, (StatusCode, Json<GenericMessage>)> {
+    require_admin(&user, "Only administrators can purge persons")?;
+    let person = match PersonModel::get_with_deleted(id, &state.database_connection).await {
+        Ok(person) => person,
+        Err(_) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(GenericMessage::new(404, "Person not found".to_string())),
+            ))
+        }
+    };
+    check_if_match(&headers, person.version)?;
+    match person
+        .delete_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(_) => Ok(Json(GenericMessage::new(
+            200,
+            "Person purged successfully".to_string(),
+        ))),
         Err(error) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(GenericMessage::new(500, error.to_string())),
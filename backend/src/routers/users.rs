@@ -1,19 +1,33 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    routing::{delete, get, put},
-    Json, Router,
+    extract::{OriginalUri, Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::{delete, get, post, put},
+    Extension, Json, Router,
+};
+use database::{
+    models::audit_event::AuditContext,
+    models::user::{UserFilter, UserModel},
+    pagination::{Page, PageRequest},
+    traits::audit::Audit,
+    traits::database::Database,
+    traits::paginate::Paginate,
 };
-use database::{models::user::UserModel, traits::database::Database, traits::persist::Persist};
 
-use crate::{messages::GenericMessage, state::ApplicationState};
+use crate::{
+    messages::GenericMessage,
+    pagination::link_headers,
+    preconditions::{check_if_match, validators, write_error},
+    routers::persons::require_admin,
+    state::ApplicationState,
+};
 
 pub fn get_router() -> Router<ApplicationState> {
     Router::new()
         .route("/users", get(list_users))
-        .route("/users/:id", get(get_user))
-        .route("/users/:id", put(update_user))
-        .route("/users/:id", delete(delete_user))
+        .route("/users/{id}", get(get_user))
+        .route("/users/{id}", put(update_user))
+        .route("/users/{id}", delete(delete_user))
+        .route("/users/{id}/restore", post(restore_user))
 }
 
 // #[utoipa::path(
@@ -29,10 +43,16 @@ pub fn get_router() -> Router<ApplicationState> {
 // )]
 pub async fn list_users(
     State(state): State<ApplicationState>,
-) -> Result<Json<Vec<UserModel>>, (StatusCode, String)> {
-    let users = UserModel::list(&state.database_connection).await;
+    OriginalUri(uri): OriginalUri,
+    Query(page): Query<PageRequest>,
+    Query(filter): Query<UserFilter>,
+) -> Result<(HeaderMap, Json<Page<UserModel>>), (StatusCode, String)> {
+    if let Err(message) = page.validate(UserModel::SORT_COLUMNS) {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, message));
+    }
+    let users = UserModel::paginate(&page, &filter, &state.database_connection).await;
     match users {
-        Ok(users) => Ok(Json(users)),
+        Ok(users) => Ok((link_headers(&uri, &users), Json(users))),
         Err(error) => Err((StatusCode::NOT_FOUND, error.to_string())),
     }
 }
@@ -40,40 +60,114 @@ pub async fn list_users(
 async fn get_user(
     State(state): State<ApplicationState>,
     Path(id): Path<u64>,
-) -> Result<Json<UserModel>, (StatusCode, String)> {
+) -> Result<(HeaderMap, Json<UserModel>), (StatusCode, String)> {
     let user = UserModel::get(id, &state.database_connection).await;
     match user {
-        Ok(user) => Ok(Json(user)),
+        Ok(user) => Ok((
+            validators(user.version, user.updated_at.unwrap_or(user.created_at)),
+            Json(user),
+        )),
         Err(error) => Err((StatusCode::NOT_FOUND, error.to_string())),
     }
 }
 
 async fn update_user(
     State(state): State<ApplicationState>,
-    Json(user): Json<UserModel>,
-) -> Result<Json<UserModel>, (StatusCode, String)> {
-    match user.update(&state.database_connection).await {
-        Ok(user) => Ok(Json(user)),
-        Err(error) => Err((StatusCode::NOT_FOUND, error.to_string())),
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+    Json(mut user): Json<UserModel>,
+) -> Result<(HeaderMap, Json<UserModel>), (StatusCode, String)> {
+    if user.id != id {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "The user id in the body does not match the one in the path".to_string(),
+        ));
+    }
+    let current = match UserModel::get(id, &state.database_connection).await {
+        Ok(current) => current,
+        Err(error) => return Err((StatusCode::NOT_FOUND, error.to_string())),
+    };
+    if let Err((status, Json(message))) = check_if_match(&headers, current.version) {
+        return Err((status, message.message));
+    }
+    user.version = current.version;
+    match user
+        .update_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(user) => Ok((
+            validators(user.version, user.updated_at.unwrap_or(user.created_at)),
+            Json(user),
+        )),
+        Err(error) => {
+            let (status, Json(message)) = write_error(&error);
+            Err((status, message.message))
+        }
     }
 }
 
 async fn delete_user(
     State(state): State<ApplicationState>,
+    Extension(audit): Extension<AuditContext>,
     Path(id): Path<u64>,
+    headers: HeaderMap,
 ) -> Result<Json<GenericMessage>, (StatusCode, String)> {
     // First, get the user from the database.
     let user = match UserModel::get(id, &state.database_connection).await {
         Ok(user) => user,
         Err(error) => return Err((StatusCode::NOT_FOUND, error.to_string())),
     };
+    if let Err((status, Json(message))) = check_if_match(&headers, user.version) {
+        return Err((status, message.message));
+    }
 
     // Then, try to delete the user.
-    match user.delete(&state.database_connection).await {
-        Ok(_) => Ok(Json(GenericMessage::new(
-            200,
-            "User deleted successfully".to_string(),
-        ))),
-        Err(error) => Err((StatusCode::NOT_FOUND, error.to_string())),
+    match user
+        .soft_delete_audited(&audit, &state.database_connection)
+        .await
+    {
+        Ok(_) => {
+            state.remove_user_cache(&user.api_token);
+            Ok(Json(GenericMessage::new(
+                200,
+                "User deleted successfully".to_string(),
+            )))
+        }
+        Err(error) => {
+            let (status, Json(message)) = write_error(&*error);
+            Err((status, message.message))
+        }
+    }
+}
+
+/// Brings back a soft-deleted user. Admins only.
+async fn restore_user(
+    State(state): State<ApplicationState>,
+    Extension(user): Extension<UserModel>,
+    Extension(audit): Extension<AuditContext>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<UserModel>), (StatusCode, String)> {
+    if let Err((status, Json(message))) =
+        require_admin(&user, "Only administrators can restore users")
+    {
+        return Err((status, message.message));
+    }
+    let deleted = match UserModel::get_with_deleted(id, &state.database_connection).await {
+        Ok(deleted) => deleted,
+        Err(_) => return Err((StatusCode::NOT_FOUND, "Deleted user not found".to_string())),
+    };
+    if let Err((status, Json(message))) = check_if_match(&headers, deleted.version) {
+        return Err((status, message.message));
+    }
+    match UserModel::restore_audited(id, deleted.version, &audit, &state.database_connection).await
+    {
+        Ok(Some(user)) => Ok((
+            validators(user.version, user.updated_at.unwrap_or(user.created_at)),
+            Json(user),
+        )),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Deleted user not found".to_string())),
+        Err(error) => Err((StatusCode::INTERNAL_SERVER_ERROR, error.to_string())),
     }
 }
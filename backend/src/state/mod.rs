@@ -1,10 +1,12 @@
 use std::{
     collections::HashMap,
+    env,
     sync::{Arc, RwLock},
 };
 
-use cep_service::structs::service::CepService;
-use database::{models::user::UserModel, pool::connect};
+use cache::service::CachedCepServer;
+use cep_service::{providers, structs::service::CepService, traits::retrievable::Retrievable};
+use database::{models::user::UserModel, pool::connect, providers::DatabaseCepProvider};
 use sqlx::MySqlPool;
 
 #[derive(Debug, Clone)]
@@ -16,10 +18,32 @@ pub struct ApplicationState {
 
 impl ApplicationState {
     pub async fn new() -> Self {
+        let database_connection = connect().await.unwrap();
+        let cep_providers = providers::build_chain(
+            &providers::names_from_env(),
+            |name| -> Option<Arc<dyn Retrievable>> {
+                match name {
+                    "database" => Some(Arc::new(DatabaseCepProvider::new(
+                        database_connection.clone(),
+                    ))),
+                    _ => None,
+                }
+            },
+        );
+        let mut cep_service = CepService::with_providers(cep_providers);
+        if env::var("REDIS_URL").is_ok() {
+            // The shared cache is best-effort, so an unreachable Redis only costs the second level.
+            match CachedCepServer::new().await {
+                Ok(cached_cep_server) => {
+                    cep_service = cep_service.with_cache(Arc::new(cached_cep_server));
+                }
+                Err(error) => eprintln!("Starting without the Redis CEP cache: {}", error),
+            }
+        }
         Self {
-            database_connection: connect().await.unwrap(),
+            database_connection,
             user_cache: Arc::new(RwLock::new(HashMap::new())),
-            cep_service: CepService::new(),
+            cep_service,
         }
     }
 }
@@ -39,4 +63,9 @@ impl ApplicationState {
         let mut cache = self.user_cache.write().unwrap();
         cache.insert(token.to_string(), user.clone());
     }
+
+    pub fn remove_user_cache(&self, token: &str) {
+        let mut cache = self.user_cache.write().unwrap();
+        cache.remove(token);
+    }
 }
@@ -1,28 +1,129 @@
-use std::env;
+use std::{env, time::Duration};
 
+use async_trait::async_trait;
 use cep_service::{
-    responses::{cep::CepResponse, service::CepServiceResponse},
-    structs::cep::Cep,
+    responses::service::CepServiceResponse, structs::cep::Cep, traits::cacheable::CepCache,
 };
 use dotenv::dotenv;
-use redis::cluster::ClusterClient;
+use redis::{
+    aio::ConnectionManager, cluster::ClusterClient, cluster_async::ClusterConnection,
+    AsyncCommands, Client, ErrorKind, RedisError, RedisResult,
+};
+
+#[derive(Clone)]
+enum RedisConnection {
+    Single(Box<ConnectionManager>),
+    Cluster(ClusterConnection),
+}
 
+/// Redis backed cache for CEP lookups, shared by every backend instance.
+///
+/// `REDIS_URL` holds one node URL, or a comma separated list of cluster nodes. Setting
+/// `REDIS_CLUSTER=true` forces cluster mode even with a single seed node.
+#[derive(Clone)]
 pub struct CachedCepServer {
-    client: ClusterClient,
+    connection: RedisConnection,
+    found_ttl: Duration,
+    not_found_ttl: Duration,
 }
 
 impl CachedCepServer {
-    pub fn new() -> Self {
+    pub async fn new() -> RedisResult<Self> {
         dotenv().ok();
-        let cluster = env::var("DATABASE_URL")
-            .expect("DATABASE_URL must be set")
+        let nodes: Vec<String> = env::var("REDIS_URL")
+            .map_err(|_| config_error("REDIS_URL must be set"))?
             .split(',')
-            .map(|a| a.to_string())
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
             .collect();
-        Self {
-            client: ClusterClient::new(cluster).unwrap(),
+        let cluster = env::var("REDIS_CLUSTER")
+            .map(|value| value == "true")
+            .unwrap_or(false);
+        let found_ttl = ttl_from_env("REDIS_CEP_TTL_SECS", 604800)?;
+        let not_found_ttl = ttl_from_env("REDIS_CEP_NOT_FOUND_TTL_SECS", 3600)?;
+
+        let connection = match (cluster || nodes.len() > 1, nodes.first()) {
+            (_, None) => return Err(config_error("REDIS_URL lists no node")),
+            (true, _) => {
+                RedisConnection::Cluster(ClusterClient::new(nodes)?.get_async_connection().await?)
+            }
+            (false, Some(node)) => RedisConnection::Single(Box::new(
+                Client::open(node.as_str())?
+                    .get_connection_manager()
+                    .await?,
+            )),
+        };
+
+        Ok(Self {
+            connection,
+            found_ttl: Duration::from_secs(found_ttl),
+            not_found_ttl: Duration::from_secs(not_found_ttl),
+        })
+    }
+
+    fn key(cep: &Cep) -> String {
+        format!("cep:{}", cep.digits())
+    }
+
+    pub async fn get_cep(&self, cep: &Cep) -> RedisResult<Option<CepServiceResponse>> {
+        let key = Self::key(cep);
+        let json: Option<String> = match self.connection.clone() {
+            RedisConnection::Single(mut connection) => connection.as_mut().get(key).await?,
+            RedisConnection::Cluster(mut connection) => connection.get(key).await?,
+        };
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    pub async fn set_cep(&self, cep: &Cep, response: &CepServiceResponse) -> RedisResult<()> {
+        let key = Self::key(cep);
+        let json = serde_json::to_string(response)?;
+        let ttl = match response {
+            CepServiceResponse::CepFound(_) => self.found_ttl,
+            CepServiceResponse::CepNotFound(_) => self.not_found_ttl,
+        }
+        .as_secs() as usize;
+        match self.connection.clone() {
+            RedisConnection::Single(mut connection) => {
+                connection.as_mut().set_ex(key, json, ttl).await
+            }
+            RedisConnection::Cluster(mut connection) => connection.set_ex(key, json, ttl).await,
         }
     }
+}
+
+fn config_error(message: &'static str) -> RedisError {
+    RedisError::from((ErrorKind::InvalidClientConfig, message))
+}
+
+fn ttl_from_env(name: &str, default: u64) -> RedisResult<u64> {
+    match env::var(name) {
+        Ok(value) => value.parse().map_err(|_| {
+            RedisError::from((
+                ErrorKind::InvalidClientConfig,
+                "TTL must be a number of seconds",
+                name.to_string(),
+            ))
+        }),
+        Err(_) => Ok(default),
+    }
+}
 
-    pub fn get_cep(cep: Cep) -> CepServiceResponse {}
+impl std::fmt::Debug for CachedCepServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedCepServer")
+            .field("found_ttl", &self.found_ttl)
+            .field("not_found_ttl", &self.not_found_ttl)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CepCache for CachedCepServer {
+    async fn get(&self, cep: &Cep) -> Option<CepServiceResponse> {
+        self.get_cep(cep).await.ok().flatten()
+    }
+
+    async fn set(&self, cep: &Cep, response: &CepServiceResponse) {
+        let _ = self.set_cep(cep, response).await;
+    }
 }
@@ -0,0 +1,78 @@
+use std::{error::Error, fmt::Display};
+
+/// Everything that can go wrong while validating or resolving a CEP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CepServiceError {
+    /// The CEP is malformed and was never sent upstream.
+    InvalidInput(String),
+    /// Every provider that answered reported the CEP as unknown.
+    NotFound,
+    /// The provider refused the request because of rate limiting.
+    RateLimited { provider: &'static str },
+    /// The provider could not be reached or answered with an unexpected status.
+    Transport {
+        provider: &'static str,
+        message: String,
+    },
+    /// The provider did not answer within its configured timeout.
+    Timeout { provider: &'static str },
+    /// The provider answered with a payload that could not be parsed.
+    Decode {
+        provider: &'static str,
+        message: String,
+    },
+    /// No provider is configured or all of them have an open circuit breaker.
+    Unavailable,
+}
+
+impl CepServiceError {
+    pub fn transport(provider: &'static str, error: impl Display) -> Self {
+        Self::Transport {
+            provider,
+            message: error.to_string(),
+        }
+    }
+
+    pub fn decode(provider: &'static str, error: impl Display) -> Self {
+        Self::Decode {
+            provider,
+            message: error.to_string(),
+        }
+    }
+
+    /// Whether the error says something about the provider's health rather than about the CEP,
+    /// making it worth a retry and counting against the provider's circuit breaker.
+    pub fn is_provider_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited { .. }
+                | Self::Transport { .. }
+                | Self::Timeout { .. }
+                | Self::Decode { .. }
+        )
+    }
+}
+
+impl Display for CepServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidInput(message) => write!(f, "Invalid CEP: {}", message),
+            Self::NotFound => write!(f, "CEP not found"),
+            Self::RateLimited { provider } => write!(f, "{} rate limit exceeded", provider),
+            Self::Transport { provider, message } => {
+                write!(f, "{} request failed: {}", provider, message)
+            }
+            Self::Timeout { provider } => write!(f, "{} timed out", provider),
+            Self::Decode { provider, message } => {
+                write!(
+                    f,
+                    "{} returned an unexpected payload: {}",
+                    provider, message
+                )
+            }
+            Self::Unavailable => write!(f, "No CEP provider available"),
+        }
+    }
+}
+
+impl Error for CepServiceError {}
@@ -0,0 +1,184 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+use crate::{responses::cep::CepResponse, structs::cep::Cep};
+
+/// Records read from a dump, and how many rows were left out for carrying an invalid CEP.
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub records: Vec<CepResponse>,
+    pub skipped: usize,
+}
+
+impl Imported {
+    /// Keeps `record` when its CEP is valid, normalized to ViaCEP's `00000-000` shape.
+    fn push(&mut self, mut record: CepResponse) {
+        match Cep::from_str(&record.cep) {
+            Ok(cep) => {
+                record.cep = cep.to_string();
+                self.records.push(record);
+            }
+            Err(_) => self.skipped += 1,
+        }
+    }
+}
+
+/// A row of a community CSV dump. Column names follow ViaCEP, with the usual English and
+/// Portuguese variants accepted as aliases.
+#[derive(Debug, Deserialize)]
+struct CsvRecord {
+    cep: String,
+    #[serde(default, alias = "street", alias = "endereco")]
+    logradouro: String,
+    #[serde(default)]
+    complemento: String,
+    #[serde(default, alias = "neighborhood", alias = "district")]
+    bairro: String,
+    #[serde(alias = "cidade", alias = "city")]
+    localidade: String,
+    #[serde(alias = "estado", alias = "state")]
+    uf: String,
+    #[serde(default, alias = "ibge_code")]
+    ibge: String,
+    #[serde(default)]
+    gia: String,
+    #[serde(default)]
+    ddd: String,
+    #[serde(default)]
+    siafi: String,
+}
+
+impl From<CsvRecord> for CepResponse {
+    fn from(record: CsvRecord) -> Self {
+        Self {
+            cep: record.cep,
+            logradouro: record.logradouro,
+            complemento: record.complemento,
+            bairro: record.bairro,
+            localidade: record.localidade,
+            uf: record.uf,
+            ibge: record.ibge,
+            gia: record.gia,
+            ddd: record.ddd,
+            siafi: record.siafi,
+        }
+    }
+}
+
+/// Reads a CSV dump with a header row.
+pub fn read_csv<P: AsRef<Path>>(path: P, delimiter: u8) -> Result<Imported, Box<dyn Error>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_path(path)?;
+    let mut imported = Imported::default();
+    for record in reader.deserialize::<CsvRecord>() {
+        imported.push(CepResponse::from(record?));
+    }
+    Ok(imported)
+}
+
+/// Reads a Correios e-DNE file, which is ISO-8859-1 encoded and `@` delimited.
+fn read_edne_file(path: &Path) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let bytes = fs::read(path)?;
+    let text: String = bytes.iter().map(|&byte| byte as char).collect();
+    Ok(text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split('@')
+                .map(|field| field.trim().to_string())
+                .collect()
+        })
+        .collect())
+}
+
+fn field(row: &[String], index: usize) -> String {
+    row.get(index).cloned().unwrap_or_default()
+}
+
+fn edne_files(directory: &Path, prefix: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.to_uppercase().starts_with(prefix))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// Reads the delimited e-DNE distribution from a directory holding `LOG_LOCALIDADE.TXT`,
+/// `LOG_BAIRRO.TXT` and the `LOG_LOGRADOURO_XX.TXT` files. Localities with a single CEP are
+/// imported as well, without street or neighborhood.
+pub fn read_edne<P: AsRef<Path>>(directory: P) -> Result<Imported, Box<dyn Error>> {
+    let directory = directory.as_ref();
+
+    // LOC_NU @ UFE_SG @ LOC_NO @ CEP @ LOC_IN_SIT @ LOC_IN_TIPO_LOC @ LOC_NU_SUB @ LOC_NO_ABREV @ MUN_NU
+    let mut localities = HashMap::new();
+    let mut imported = Imported::default();
+    for path in edne_files(directory, "LOG_LOCALIDADE")? {
+        for row in read_edne_file(&path)? {
+            let cep = field(&row, 3);
+            if !cep.is_empty() {
+                imported.push(CepResponse {
+                    cep,
+                    logradouro: String::new(),
+                    complemento: String::new(),
+                    bairro: String::new(),
+                    localidade: field(&row, 2),
+                    uf: field(&row, 1),
+                    ibge: field(&row, 8),
+                    gia: String::new(),
+                    ddd: String::new(),
+                    siafi: String::new(),
+                });
+            }
+            localities.insert(field(&row, 0), (field(&row, 2), field(&row, 8)));
+        }
+    }
+
+    // BAI_NU @ UFE_SG @ LOC_NU @ BAI_NO @ BAI_NO_ABREV
+    let mut neighborhoods = HashMap::new();
+    for path in edne_files(directory, "LOG_BAIRRO")? {
+        for row in read_edne_file(&path)? {
+            neighborhoods.insert(field(&row, 0), field(&row, 3));
+        }
+    }
+
+    // LOG_NU @ UFE_SG @ LOC_NU @ BAI_NU_INI @ BAI_NU_FIM @ LOG_NO @ LOG_COMPLEMENTO @ CEP @ TLO_TX @ LOG_STA_TLO @ LOG_NO_ABREV
+    for path in edne_files(directory, "LOG_LOGRADOURO")? {
+        for row in read_edne_file(&path)? {
+            let (localidade, ibge) = localities.get(&field(&row, 2)).cloned().unwrap_or_default();
+            let logradouro = format!("{} {}", field(&row, 8), field(&row, 5))
+                .trim()
+                .to_string();
+            imported.push(CepResponse {
+                cep: field(&row, 7),
+                logradouro,
+                complemento: field(&row, 6),
+                bairro: neighborhoods
+                    .get(&field(&row, 3))
+                    .cloned()
+                    .unwrap_or_default(),
+                localidade,
+                uf: field(&row, 1),
+                ibge,
+                gia: String::new(),
+                ddd: String::new(),
+                siafi: String::new(),
+            });
+        }
+    }
+
+    Ok(imported)
+}
@@ -1,3 +1,6 @@
+pub mod error;
+pub mod import;
+pub mod providers;
 pub mod responses;
 pub mod structs;
 pub mod traits;
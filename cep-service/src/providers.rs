@@ -0,0 +1,115 @@
+pub mod brasilapi;
+pub mod offline;
+pub mod postmon;
+pub mod viacep;
+
+use std::{env, error::Error, sync::Arc};
+
+use reqwest::{
+    header::{self, HeaderMap},
+    StatusCode,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{error::CepServiceError, traits::retrievable::Retrievable};
+
+use self::{
+    brasilapi::BrasilApiProvider, offline::OfflineProvider, postmon::PostmonProvider,
+    viacep::ViaCepProvider,
+};
+
+/// Provider names listed in the comma separated `CEP_PROVIDERS` setting, defaulting to ViaCEP.
+pub fn names_from_env() -> Vec<String> {
+    env::var("CEP_PROVIDERS")
+        .unwrap_or("viacep".to_string())
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Builds a provider from its configuration name (`viacep`, `brasilapi`, `postmon` or `offline`).
+/// Unknown names give `Ok(None)`; an error means the provider is known but could not be set up.
+pub fn from_name(name: &str) -> Result<Option<Arc<dyn Retrievable>>, Box<dyn Error>> {
+    let provider: Arc<dyn Retrievable> = match name.trim().to_lowercase().as_str() {
+        "viacep" => Arc::new(ViaCepProvider::new()),
+        "brasilapi" => Arc::new(BrasilApiProvider::new()),
+        "postmon" => Arc::new(PostmonProvider::new()),
+        "offline" => Arc::new(offline_from_env()?),
+        _ => return Ok(None),
+    };
+    Ok(Some(provider))
+}
+
+/// Loads the table named by `CEP_OFFLINE_TABLE`.
+pub fn offline_from_env() -> Result<OfflineProvider, Box<dyn Error>> {
+    let path = env::var("CEP_OFFLINE_TABLE")
+        .map_err(|error| format!("CEP_OFFLINE_TABLE must be set: {}", error))?;
+    OfflineProvider::from_csv(&path)
+        .map_err(|error| format!("Failed to load offline CEP table {}: {}", path, error).into())
+}
+
+/// Resolves the configured provider names, leaving out unknown ones and the ones that fail to
+/// load. `overrides` is asked first, so callers can supply or replace providers by name.
+pub fn build_chain<F>(names: &[String], mut overrides: F) -> Vec<Arc<dyn Retrievable>>
+where
+    F: FnMut(&str) -> Option<Arc<dyn Retrievable>>,
+{
+    names
+        .iter()
+        .filter_map(|name| {
+            if let Some(provider) = overrides(name) {
+                return Some(provider);
+            }
+            match from_name(name) {
+                Ok(Some(provider)) => Some(provider),
+                Ok(None) => {
+                    eprintln!("Skipping unknown CEP provider {}", name);
+                    None
+                }
+                Err(error) => {
+                    eprintln!("Skipping CEP provider {}: {}", name, error);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Performs a JSON GET against a provider, translating HTTP statuses into `CepServiceError`.
+pub(crate) async fn fetch_json<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    provider: &'static str,
+    url: &str,
+) -> Result<T, CepServiceError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ACCEPT, "application/json".parse().unwrap());
+    let response = client
+        .get(url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|error| CepServiceError::transport(provider, error))?;
+    match response.status() {
+        StatusCode::TOO_MANY_REQUESTS => return Err(CepServiceError::RateLimited { provider }),
+        StatusCode::NOT_FOUND => return Err(CepServiceError::NotFound),
+        StatusCode::BAD_REQUEST => {
+            return Err(CepServiceError::InvalidInput(format!(
+                "{} rejected the request",
+                provider
+            )))
+        }
+        status if !status.is_success() => {
+            return Err(CepServiceError::transport(
+                provider,
+                format!("unexpected status {}", status),
+            ))
+        }
+        _ => {}
+    }
+    let json = response
+        .text()
+        .await
+        .map_err(|error| CepServiceError::transport(provider, error))?;
+    serde_json::from_str::<T>(&json).map_err(|error| CepServiceError::decode(provider, error))
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+use crate::{
+    error::CepServiceError,
+    responses::{brasilapi::BrasilApiResponse, cep::CepResponse},
+    structs::cep::Cep,
+    traits::retrievable::Retrievable,
+};
+
+use super::fetch_json;
+
+#[derive(Debug, Clone, Default)]
+pub struct BrasilApiProvider {
+    client: reqwest::Client,
+}
+
+impl BrasilApiProvider {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Retrievable for BrasilApiProvider {
+    fn name(&self) -> &'static str {
+        "brasilapi"
+    }
+
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        let url = format!("https://brasilapi.com.br/api/cep/v1/{}", cep.digits());
+        let address = fetch_json::<BrasilApiResponse>(&self.client, self.name(), &url).await?;
+        Ok(address.into())
+    }
+}
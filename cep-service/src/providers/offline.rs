@@ -0,0 +1,76 @@
+use std::{collections::HashMap, error::Error, path::Path};
+
+use async_trait::async_trait;
+
+use crate::{
+    error::CepServiceError,
+    import,
+    responses::cep::CepResponse,
+    structs::{cep::Cep, search::AddressQuery},
+    traits::retrievable::Retrievable,
+};
+
+/// Resolves CEPs from an in-memory table, without any network access.
+#[derive(Debug, Clone, Default)]
+pub struct OfflineProvider {
+    table: HashMap<String, CepResponse>,
+}
+
+impl OfflineProvider {
+    pub fn new(entries: Vec<CepResponse>) -> Self {
+        let table = entries
+            .into_iter()
+            .map(|entry| (entry.cep.replace('-', ""), entry))
+            .collect();
+        Self { table }
+    }
+
+    /// Loads a comma separated dump, see `import::read_csv` for the accepted columns.
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(import::read_csv(path, b',')?.records))
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+}
+
+#[async_trait]
+impl Retrievable for OfflineProvider {
+    fn name(&self) -> &'static str {
+        "offline"
+    }
+
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        self.table
+            .get(&cep.digits())
+            .cloned()
+            .ok_or(CepServiceError::NotFound)
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    async fn search(&self, query: &AddressQuery) -> Result<Vec<CepResponse>, CepServiceError> {
+        let uf = query.uf.trim().to_lowercase();
+        let city = query.city.trim().to_lowercase();
+        let street = query.street.trim().to_lowercase();
+        let mut candidates = self
+            .table
+            .values()
+            .filter(|address| {
+                address.uf.to_lowercase() == uf
+                    && address.localidade.to_lowercase() == city
+                    && address.logradouro.to_lowercase().contains(&street)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| a.cep.cmp(&b.cep));
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+use crate::{
+    error::CepServiceError,
+    responses::{cep::CepResponse, postmon::PostmonResponse},
+    structs::cep::Cep,
+    traits::retrievable::Retrievable,
+};
+
+use super::fetch_json;
+
+#[derive(Debug, Clone, Default)]
+pub struct PostmonProvider {
+    client: reqwest::Client,
+}
+
+impl PostmonProvider {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Retrievable for PostmonProvider {
+    fn name(&self) -> &'static str {
+        "postmon"
+    }
+
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        let url = format!("https://api.postmon.com.br/v1/cep/{}", cep.digits());
+        let address = fetch_json::<PostmonResponse>(&self.client, self.name(), &url).await?;
+        Ok(address.into())
+    }
+}
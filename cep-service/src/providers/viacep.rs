@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+
+use crate::{
+    error::CepServiceError,
+    responses::{cep::CepResponse, service::CepServiceResponse},
+    structs::{cep::Cep, search::AddressQuery},
+    traits::retrievable::Retrievable,
+};
+
+use super::fetch_json;
+
+#[derive(Debug, Clone, Default)]
+pub struct ViaCepProvider {
+    client: reqwest::Client,
+}
+
+impl ViaCepProvider {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Retrievable for ViaCepProvider {
+    fn name(&self) -> &'static str {
+        "viacep"
+    }
+
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        let url = format!("https://viacep.com.br/ws/{}/json/", cep.digits());
+        match fetch_json::<CepServiceResponse>(&self.client, self.name(), &url).await? {
+            CepServiceResponse::CepFound(address) => Ok(address),
+            CepServiceResponse::CepNotFound(_) => Err(CepServiceError::NotFound),
+        }
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    async fn search(&self, query: &AddressQuery) -> Result<Vec<CepResponse>, CepServiceError> {
+        let mut url = reqwest::Url::parse("https://viacep.com.br/ws/").unwrap();
+        url.path_segments_mut().unwrap().pop_if_empty().extend([
+            query.uf.trim(),
+            query.city.trim(),
+            query.street.trim(),
+            "json",
+            "",
+        ]);
+        fetch_json::<Vec<CepResponse>>(&self.client, self.name(), url.as_str()).await
+    }
+}
@@ -1,3 +1,5 @@
+pub mod brasilapi;
 pub mod cep;
 pub mod error;
+pub mod postmon;
 pub mod service;
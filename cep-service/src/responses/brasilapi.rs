@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+use super::cep::CepResponse;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BrasilApiResponse {
+    pub cep: String,
+    pub state: String,
+    pub city: String,
+    pub neighborhood: Option<String>,
+    pub street: Option<String>,
+}
+
+impl From<BrasilApiResponse> for CepResponse {
+    fn from(response: BrasilApiResponse) -> Self {
+        Self {
+            cep: response.cep,
+            logradouro: response.street.unwrap_or_default(),
+            complemento: String::new(),
+            bairro: response.neighborhood.unwrap_or_default(),
+            localidade: response.city,
+            uf: response.state,
+            ibge: String::new(),
+            gia: String::new(),
+            ddd: String::new(),
+            siafi: String::new(),
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CepResponse {
-    // pub cep: String,
+    #[serde(default)]
+    pub cep: String,
     pub logradouro: String,
     pub complemento: String,
     pub bairro: String,
@@ -0,0 +1,39 @@
+use serde::Deserialize;
+
+use super::cep::CepResponse;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PostmonCityInfo {
+    pub codigo_ibge: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PostmonResponse {
+    pub cep: String,
+    pub logradouro: Option<String>,
+    pub complemento: Option<String>,
+    pub bairro: Option<String>,
+    pub cidade: String,
+    pub estado: String,
+    pub cidade_info: Option<PostmonCityInfo>,
+}
+
+impl From<PostmonResponse> for CepResponse {
+    fn from(response: PostmonResponse) -> Self {
+        Self {
+            cep: response.cep,
+            logradouro: response.logradouro.unwrap_or_default(),
+            complemento: response.complemento.unwrap_or_default(),
+            bairro: response.bairro.unwrap_or_default(),
+            localidade: response.cidade,
+            uf: response.estado,
+            ibge: response
+                .cidade_info
+                .and_then(|info| info.codigo_ibge)
+                .unwrap_or_default(),
+            gia: String::new(),
+            ddd: String::new(),
+            siafi: String::new(),
+        }
+    }
+}
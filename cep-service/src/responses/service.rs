@@ -1,10 +1,22 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CepServiceError;
 
 use super::{cep::CepResponse, error::CepError};
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum CepServiceResponse {
     CepNotFound(CepError),
     CepFound(CepResponse),
 }
+
+impl CepServiceResponse {
+    pub fn into_result(self) -> Result<CepResponse, CepServiceError> {
+        match self {
+            Self::CepFound(address) => Ok(address),
+            Self::CepNotFound(_) => Err(CepServiceError::NotFound),
+        }
+    }
+}
@@ -1,2 +1,7 @@
+pub mod breaker;
 pub mod cep;
+pub mod geography;
+pub mod memory_cache;
+pub mod policy;
+pub mod search;
 pub mod service;
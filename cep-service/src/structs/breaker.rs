@@ -0,0 +1,101 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct BreakerCounters {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the half-open trial request went out, while it has not reported back.
+    probe_started_at: Option<Instant>,
+}
+
+/// Marks a provider unhealthy after `threshold` consecutive failures and keeps it out of the
+/// rotation for `cooldown`, after which a single trial request is let through. Other callers
+/// are turned away until that trial reports back, or until another `cooldown` has passed in
+/// case it was dropped before it could.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    counters: Mutex<BreakerCounters>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            counters: Mutex::new(BreakerCounters::default()),
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.state_of(&self.counters.lock().unwrap())
+    }
+
+    fn state_of(&self, counters: &BreakerCounters) -> BreakerState {
+        match counters.opened_at {
+            None => BreakerState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => BreakerState::Open,
+            Some(_) => BreakerState::HalfOpen,
+        }
+    }
+
+    /// Whether the caller may send a request. In the half-open state only the first caller
+    /// is admitted, as the trial request.
+    pub fn allows_request(&self) -> bool {
+        let mut counters = self.counters.lock().unwrap();
+        match self.state_of(&counters) {
+            BreakerState::Closed => true,
+            BreakerState::Open => false,
+            BreakerState::HalfOpen => {
+                let probing = counters
+                    .probe_started_at
+                    .is_some_and(|started_at| started_at.elapsed() < self.cooldown);
+                if !probing {
+                    counters.probe_started_at = Some(Instant::now());
+                }
+                !probing
+            }
+        }
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.counters.lock().unwrap().consecutive_failures
+    }
+
+    pub fn record_success(&self) {
+        let mut counters = self.counters.lock().unwrap();
+        counters.consecutive_failures = 0;
+        counters.opened_at = None;
+        counters.probe_started_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut counters = self.counters.lock().unwrap();
+        counters.consecutive_failures += 1;
+        counters.probe_started_at = None;
+        if counters.consecutive_failures >= self.threshold {
+            counters.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealth {
+    pub name: &'static str,
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+}
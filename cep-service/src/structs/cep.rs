@@ -1,66 +1,261 @@
-use std::{error::Error, fmt::Display};
+use std::{fmt::Display, str::FromStr};
 
 use serde::{Deserialize, Serialize};
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    mysql::{MySqlTypeInfo, MySqlValueRef},
+    Decode, Encode, MySql, Type,
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Cep {
-    region: u8,
-    subregion: u8,
-    sector: u8,
-    subsector: u8,
-    division: u8,
-    distribution: u16,
-}
+use crate::error::CepServiceError;
+
+use super::geography::{self, Locality, Region, UfRange};
+
+/// A validated CEP, kept as its eight digit number.
+///
+/// Parses from `01001-000` or `01001000`, displays as `01001-000`, and serializes (JSON and
+/// MySQL alike) as the bare digits `01001000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Cep(u32);
 
 impl Cep {
-    pub fn new(cep: String) -> Result<Self, Box<dyn Error>> {
-        let cep = cep.replace("-", "");
-        let region = cep[0..1].parse::<u8>().unwrap();
-        let subregion = cep[1..2].parse::<u8>().unwrap();
-        let sector = cep[2..3].parse::<u8>().unwrap();
-        let subsector = cep[3..4].parse::<u8>().unwrap();
-        let division = cep[4..5].parse::<u8>().unwrap();
-        let distribution = cep[5..8].parse::<u16>().unwrap();
-        match distribution {
-            1..=999 => Ok(Self {
-                region,
-                subregion,
-                sector,
-                subsector,
-                division,
-                distribution,
-            }),
-            _ => Err("Invalid CEP".into()),
+    /// Lowest CEP in use; everything below `01000-000` is unassigned.
+    const MIN: u32 = 1_000_000;
+    const MAX: u32 = 99_999_999;
+
+    pub fn new(cep: String) -> Result<Self, CepServiceError> {
+        cep.parse()
+    }
+
+    /// The CEP as eight digits, e.g. `01001000`.
+    pub fn digits(&self) -> String {
+        format!("{:08}", self.0)
+    }
+
+    fn digit(&self, position: u32) -> u8 {
+        ((self.0 / 10u32.pow(7 - position)) % 10) as u8
+    }
+
+    pub fn region(&self) -> u8 {
+        self.digit(0)
+    }
+
+    pub fn subregion(&self) -> u8 {
+        self.digit(1)
+    }
+
+    pub fn sector(&self) -> u8 {
+        self.digit(2)
+    }
+
+    pub fn subsector(&self) -> u8 {
+        self.digit(3)
+    }
+
+    pub fn division(&self) -> u8 {
+        self.digit(4)
+    }
+
+    pub fn distribution(&self) -> u16 {
+        (self.0 % 1000) as u16
+    }
+
+    /// The Correios block of CEPs this one belongs to, looked up without any network access.
+    pub fn uf_range(&self) -> Option<&'static UfRange> {
+        geography::uf_range(self.0)
+    }
+
+    pub fn uf(&self) -> Option<&'static str> {
+        self.uf_range().map(|range| range.uf)
+    }
+
+    pub fn macro_region(&self) -> Option<Region> {
+        self.uf().and_then(Region::from_uf)
+    }
+
+    /// Name of the macro-region, e.g. `Sudeste` for `01001-000`.
+    pub fn region_name(&self) -> Option<&'static str> {
+        self.macro_region().map(|region| region.name())
+    }
+
+    pub fn locality(&self) -> Option<Locality> {
+        geography::locality(self.0)
+    }
+
+    /// Whether the CEP range agrees with the given UF, compared case-insensitively.
+    pub fn matches_uf(&self, uf: &str) -> bool {
+        self.uf()
+            .is_some_and(|range_uf| range_uf.eq_ignore_ascii_case(uf.trim()))
+    }
+}
+
+impl FromStr for Cep {
+    type Err = CepServiceError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let digits = match value.len() {
+            9 if value.as_bytes()[5] == b'-' => format!("{}{}", &value[0..5], &value[6..9]),
+            _ => value.to_string(),
+        };
+        if digits.len() != 8 || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(CepServiceError::InvalidInput(format!(
+                "{} must be formatted as 00000-000 or 00000000",
+                value
+            )));
+        }
+        let number = digits.parse::<u32>().unwrap();
+        match number {
+            Self::MIN..=Self::MAX => Ok(Self(number)),
+            _ => Err(CepServiceError::InvalidInput(format!(
+                "{} is not an assigned CEP range",
+                value
+            ))),
         }
     }
 }
 
 impl TryFrom<String> for Cep {
-    type Error = Box<dyn Error>;
+    type Error = CepServiceError;
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        Self::new(value)
+        value.parse()
     }
 }
 
 impl TryFrom<&String> for Cep {
-    type Error = Box<dyn Error>;
+    type Error = CepServiceError;
     fn try_from(value: &String) -> Result<Self, Self::Error> {
-        let value = value.clone();
-        Self::new(value)
+        value.parse()
+    }
+}
+
+impl TryFrom<&str> for Cep {
+    type Error = CepServiceError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Cep> for String {
+    fn from(cep: Cep) -> Self {
+        cep.digits()
     }
 }
 
 impl Display for Cep {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}{}{}{}{}-{}",
-            self.region,
-            self.subregion,
-            self.sector,
-            self.subsector,
-            self.division,
-            self.distribution
-        )
+        write!(f, "{:05}-{:03}", self.0 / 1000, self.0 % 1000)
+    }
+}
+
+impl Type<MySql> for Cep {
+    fn type_info() -> MySqlTypeInfo {
+        <str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, MySql> for Cep {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <String as Encode<'q, MySql>>::encode(self.digits(), buf)
+    }
+}
+
+impl<'r> Decode<'r, MySql> for Cep {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as Decode<'r, MySql>>::decode(value)?;
+        Ok(value.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_digits_keeping_leading_zeros() {
+        let cep: Cep = "01001000".parse().unwrap();
+        assert_eq!(cep.digits(), "01001000");
+        assert_eq!(cep.to_string(), "01001-000");
+    }
+
+    #[test]
+    fn parses_hyphenated_input() {
+        let cep: Cep = " 01001-000 ".parse().unwrap();
+        assert_eq!(cep, "01001000".parse().unwrap());
+        assert_eq!(cep.digits(), "01001000");
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        for value in [
+            "",
+            "0100100",
+            "010010000",
+            "0100-1000",
+            "01001-0000",
+            "1001-000",
+        ] {
+            assert!(
+                value.parse::<Cep>().is_err(),
+                "{} should be rejected",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_digits() {
+        for value in [
+            "0100100a",
+            "01001-00a",
+            "01001 000",
+            "+1001000",
+            "01001_000",
+            "0100é000",
+        ] {
+            assert!(
+                value.parse::<Cep>().is_err(),
+                "{} should be rejected",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unassigned_range() {
+        assert!("00000000".parse::<Cep>().is_err());
+        assert!("00999-999".parse::<Cep>().is_err());
+        assert!("01000-000".parse::<Cep>().is_ok());
+        assert!("99999-999".parse::<Cep>().is_ok());
+    }
+
+    #[test]
+    fn serializes_as_bare_digits() {
+        let cep: Cep = "01001-000".parse().unwrap();
+        assert_eq!(serde_json::to_string(&cep).unwrap(), "\"01001000\"");
+    }
+
+    #[test]
+    fn deserializes_either_format() {
+        let bare: Cep = serde_json::from_str("\"01001000\"").unwrap();
+        let hyphenated: Cep = serde_json::from_str("\"01001-000\"").unwrap();
+        assert_eq!(bare, hyphenated);
+    }
+
+    #[test]
+    fn deserialization_rejects_invalid_values() {
+        for json in ["\"0100100\"", "\"abcdefgh\"", "1001000", "null"] {
+            assert!(
+                serde_json::from_str::<Cep>(json).is_err(),
+                "{} should be rejected",
+                json
+            );
+        }
     }
 }
@@ -0,0 +1,214 @@
+use serde::Serialize;
+
+/// Brazilian macro-regions, as defined by IBGE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Region {
+    Norte,
+    Nordeste,
+    CentroOeste,
+    Sudeste,
+    Sul,
+}
+
+impl Region {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Norte => "Norte",
+            Self::Nordeste => "Nordeste",
+            Self::CentroOeste => "Centro-Oeste",
+            Self::Sudeste => "Sudeste",
+            Self::Sul => "Sul",
+        }
+    }
+
+    pub fn from_uf(uf: &str) -> Option<Self> {
+        match uf.to_uppercase().as_str() {
+            "AC" | "AP" | "AM" | "PA" | "RO" | "RR" | "TO" => Some(Self::Norte),
+            "AL" | "BA" | "CE" | "MA" | "PB" | "PE" | "PI" | "RN" | "SE" => Some(Self::Nordeste),
+            "DF" | "GO" | "MT" | "MS" => Some(Self::CentroOeste),
+            "ES" | "MG" | "RJ" | "SP" => Some(Self::Sudeste),
+            "PR" | "RS" | "SC" => Some(Self::Sul),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a CEP belongs to the state capital or to the rest of the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Locality {
+    Capital(&'static str),
+    Interior,
+}
+
+/// A contiguous block of CEPs, bounds included, written as eight digit numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UfRange {
+    pub uf: &'static str,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl UfRange {
+    const fn new(uf: &'static str, start: u32, end: u32) -> Self {
+        Self { uf, start, end }
+    }
+
+    pub fn contains(&self, cep: u32) -> bool {
+        (self.start..=self.end).contains(&cep)
+    }
+}
+
+/// CEP blocks per UF, as published by Correios. AM, DF, GO and RO are split in two blocks.
+pub const UF_RANGES: [UfRange; 31] = [
+    UfRange::new("SP", 1_000_000, 19_999_999),
+    UfRange::new("RJ", 20_000_000, 28_999_999),
+    UfRange::new("ES", 29_000_000, 29_999_999),
+    UfRange::new("MG", 30_000_000, 39_999_999),
+    UfRange::new("BA", 40_000_000, 48_999_999),
+    UfRange::new("SE", 49_000_000, 49_999_999),
+    UfRange::new("PE", 50_000_000, 56_999_999),
+    UfRange::new("AL", 57_000_000, 57_999_999),
+    UfRange::new("PB", 58_000_000, 58_999_999),
+    UfRange::new("RN", 59_000_000, 59_999_999),
+    UfRange::new("CE", 60_000_000, 63_999_999),
+    UfRange::new("PI", 64_000_000, 64_999_999),
+    UfRange::new("MA", 65_000_000, 65_999_999),
+    UfRange::new("PA", 66_000_000, 68_899_999),
+    UfRange::new("AP", 68_900_000, 68_999_999),
+    UfRange::new("AM", 69_000_000, 69_299_999),
+    UfRange::new("RR", 69_300_000, 69_399_999),
+    UfRange::new("AM", 69_400_000, 69_899_999),
+    UfRange::new("AC", 69_900_000, 69_999_999),
+    UfRange::new("DF", 70_000_000, 72_799_999),
+    UfRange::new("GO", 72_800_000, 72_999_999),
+    UfRange::new("DF", 73_000_000, 73_699_999),
+    UfRange::new("GO", 73_700_000, 76_799_999),
+    UfRange::new("RO", 76_800_000, 76_999_999),
+    UfRange::new("TO", 77_000_000, 77_999_999),
+    UfRange::new("MT", 78_000_000, 78_899_999),
+    UfRange::new("RO", 78_900_000, 78_999_999),
+    UfRange::new("MS", 79_000_000, 79_999_999),
+    UfRange::new("PR", 80_000_000, 87_999_999),
+    UfRange::new("SC", 88_000_000, 89_999_999),
+    UfRange::new("RS", 90_000_000, 99_999_999),
+];
+
+/// CEP blocks of each state capital.
+const CAPITAL_RANGES: [(&str, u32, u32); 28] = [
+    ("São Paulo", 1_000_000, 5_999_999),
+    ("São Paulo", 8_000_000, 8_499_999),
+    ("Rio de Janeiro", 20_000_000, 23_799_999),
+    ("Vitória", 29_000_000, 29_099_999),
+    ("Belo Horizonte", 30_000_000, 31_999_999),
+    ("Salvador", 40_000_000, 42_599_999),
+    ("Aracaju", 49_000_000, 49_099_999),
+    ("Recife", 50_000_000, 52_999_999),
+    ("Maceió", 57_000_000, 57_099_999),
+    ("João Pessoa", 58_000_000, 58_099_999),
+    ("Natal", 59_000_000, 59_139_999),
+    ("Fortaleza", 60_000_000, 61_599_999),
+    ("Teresina", 64_000_000, 64_099_999),
+    ("São Luís", 65_000_000, 65_109_999),
+    ("Belém", 66_000_000, 66_999_999),
+    ("Macapá", 68_900_000, 68_914_999),
+    ("Manaus", 69_000_000, 69_099_999),
+    ("Boa Vista", 69_300_000, 69_339_999),
+    ("Rio Branco", 69_900_000, 69_923_999),
+    ("Brasília", 70_000_000, 70_999_999),
+    ("Goiânia", 74_000_000, 74_899_999),
+    ("Porto Velho", 76_800_000, 76_834_999),
+    ("Palmas", 77_000_000, 77_249_999),
+    ("Cuiabá", 78_000_000, 78_109_999),
+    ("Campo Grande", 79_000_000, 79_124_999),
+    ("Curitiba", 80_000_000, 82_999_999),
+    ("Florianópolis", 88_000_000, 88_099_999),
+    ("Porto Alegre", 90_000_000, 91_999_999),
+];
+
+pub fn uf_range(cep: u32) -> Option<&'static UfRange> {
+    UF_RANGES.iter().find(|range| range.contains(cep))
+}
+
+pub fn locality(cep: u32) -> Option<Locality> {
+    uf_range(cep)?;
+    Some(
+        CAPITAL_RANGES
+            .iter()
+            .find(|(_, start, end)| (*start..=*end).contains(&cep))
+            .map(|(capital, _, _)| Locality::Capital(capital))
+            .unwrap_or(Locality::Interior),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_and_last_cep_of_each_range_belong_to_it() {
+        for range in UF_RANGES.iter() {
+            assert_eq!(uf_range(range.start), Some(range), "start of {}", range.uf);
+            assert_eq!(uf_range(range.end), Some(range), "end of {}", range.uf);
+        }
+    }
+
+    #[test]
+    fn ranges_are_contiguous() {
+        for pair in UF_RANGES.windows(2) {
+            assert_eq!(
+                pair[0].end + 1,
+                pair[1].start,
+                "{} to {}",
+                pair[0].uf,
+                pair[1].uf
+            );
+        }
+    }
+
+    #[test]
+    fn neighbouring_ceps_fall_in_the_next_uf() {
+        assert_eq!(uf_range(19_999_999).map(|range| range.uf), Some("SP"));
+        assert_eq!(uf_range(20_000_000).map(|range| range.uf), Some("RJ"));
+        assert_eq!(uf_range(68_899_999).map(|range| range.uf), Some("PA"));
+        assert_eq!(uf_range(68_900_000).map(|range| range.uf), Some("AP"));
+        assert_eq!(uf_range(69_299_999).map(|range| range.uf), Some("AM"));
+        assert_eq!(uf_range(69_300_000).map(|range| range.uf), Some("RR"));
+        assert_eq!(uf_range(69_400_000).map(|range| range.uf), Some("AM"));
+        assert_eq!(uf_range(72_799_999).map(|range| range.uf), Some("DF"));
+        assert_eq!(uf_range(72_800_000).map(|range| range.uf), Some("GO"));
+        assert_eq!(uf_range(73_000_000).map(|range| range.uf), Some("DF"));
+        assert_eq!(uf_range(73_700_000).map(|range| range.uf), Some("GO"));
+        assert_eq!(uf_range(78_899_999).map(|range| range.uf), Some("MT"));
+        assert_eq!(uf_range(78_900_000).map(|range| range.uf), Some("RO"));
+        assert_eq!(uf_range(79_000_000).map(|range| range.uf), Some("MS"));
+    }
+
+    #[test]
+    fn ceps_outside_every_range_have_no_uf() {
+        assert_eq!(uf_range(0), None);
+        assert_eq!(uf_range(999_999), None);
+        assert_eq!(uf_range(100_000_000), None);
+        assert_eq!(locality(999_999), None);
+    }
+
+    #[test]
+    fn capital_bounds_and_interior() {
+        assert_eq!(locality(1_000_000), Some(Locality::Capital("São Paulo")));
+        assert_eq!(locality(5_999_999), Some(Locality::Capital("São Paulo")));
+        assert_eq!(locality(6_000_000), Some(Locality::Interior));
+        assert_eq!(
+            locality(91_999_999),
+            Some(Locality::Capital("Porto Alegre"))
+        );
+        assert_eq!(locality(92_000_000), Some(Locality::Interior));
+    }
+
+    #[test]
+    fn every_range_uf_has_a_region() {
+        for range in UF_RANGES.iter() {
+            assert!(Region::from_uf(range.uf).is_some(), "{}", range.uf);
+        }
+        assert_eq!(Region::from_uf("sp"), Some(Region::Sudeste));
+        assert_eq!(Region::from_uf("XX"), None);
+    }
+}
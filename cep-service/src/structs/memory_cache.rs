@@ -0,0 +1,112 @@
+use std::{
+    env,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use serde::Serialize;
+
+use crate::responses::service::CepServiceResponse;
+
+#[derive(Debug)]
+struct CacheEntry {
+    response: CepServiceResponse,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+/// Bounded in-process LRU cache whose entries expire after a TTL that depends on whether the
+/// CEP was found, so "not found" answers are retried much sooner than addresses.
+#[derive(Debug)]
+pub struct MemoryCache {
+    entries: Mutex<LruCache<String, CacheEntry>>,
+    found_ttl: Duration,
+    not_found_ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize, found_ttl: Duration, not_found_ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            found_ttl,
+            not_found_ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Reads `CEP_CACHE_CAPACITY`, `CEP_CACHE_TTL_SECS` and `CEP_CACHE_NOT_FOUND_TTL_SECS`.
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: u64| -> u64 {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+        Self::new(
+            read("CEP_CACHE_CAPACITY", 10_000) as usize,
+            Duration::from_secs(read("CEP_CACHE_TTL_SECS", 86_400)),
+            Duration::from_secs(read("CEP_CACHE_NOT_FOUND_TTL_SECS", 300)),
+        )
+    }
+
+    pub fn get(&self, key: &str) -> Option<CepServiceResponse> {
+        let mut entries = self.entries.lock().unwrap();
+        let response = match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.response.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        };
+        match response {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        response
+    }
+
+    pub fn insert(&self, key: String, response: CepServiceResponse) {
+        let ttl = match response {
+            CepServiceResponse::CepFound(_) => self.found_ttl,
+            CepServiceResponse::CepNotFound(_) => self.not_found_ttl,
+        };
+        let entry = CacheEntry {
+            response,
+            expires_at: Instant::now() + ttl,
+        };
+        self.entries.lock().unwrap().put(key, entry);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.entries.lock().unwrap();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: entries.len(),
+            capacity: entries.cap().get(),
+        }
+    }
+}
+
+impl Default for MemoryCache {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
@@ -0,0 +1,53 @@
+use std::{env, time::Duration};
+
+/// Timeout, retry and circuit breaker settings applied to a single provider.
+#[derive(Debug, Clone)]
+pub struct ProviderPolicy {
+    pub timeout: Duration,
+    pub retries: u32,
+    pub backoff: Duration,
+    pub breaker_threshold: u32,
+    pub breaker_cooldown: Duration,
+}
+
+impl ProviderPolicy {
+    /// Reads `CEP_<PROVIDER>_<SETTING>` first and falls back to `CEP_PROVIDER_<SETTING>`,
+    /// e.g. `CEP_VIACEP_TIMEOUT_MS` overrides `CEP_PROVIDER_TIMEOUT_MS` for ViaCEP only.
+    pub fn from_env(provider: &str) -> Self {
+        let defaults = Self::default();
+        let read = |setting: &str, default: u64| -> u64 {
+            env::var(format!("CEP_{}_{}", provider.to_uppercase(), setting))
+                .or_else(|_| env::var(format!("CEP_PROVIDER_{}", setting)))
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            timeout: Duration::from_millis(read("TIMEOUT_MS", defaults.timeout.as_millis() as u64)),
+            retries: read("RETRIES", defaults.retries as u64) as u32,
+            backoff: Duration::from_millis(read("BACKOFF_MS", defaults.backoff.as_millis() as u64)),
+            breaker_threshold: read("BREAKER_THRESHOLD", defaults.breaker_threshold as u64) as u32,
+            breaker_cooldown: Duration::from_secs(read(
+                "BREAKER_COOLDOWN_SECS",
+                defaults.breaker_cooldown.as_secs(),
+            )),
+        }
+    }
+
+    /// Delay before the given retry attempt (1-based), doubling each time.
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        self.backoff * 2u32.saturating_pow(attempt.saturating_sub(1))
+    }
+}
+
+impl Default for ProviderPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(2000),
+            retries: 2,
+            backoff: Duration::from_millis(100),
+            breaker_threshold: 5,
+            breaker_cooldown: Duration::from_secs(30),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::CepServiceError;
+
+/// Address parts used to look CEPs up in reverse, mirroring ViaCEP's
+/// `/ws/{UF}/{cidade}/{logradouro}/json/` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressQuery {
+    pub uf: String,
+    pub city: String,
+    pub street: String,
+}
+
+impl AddressQuery {
+    /// Applies ViaCEP's rules: a two letter UF and at least three characters for city and street.
+    pub fn validate(&self) -> Result<(), CepServiceError> {
+        if self.uf.trim().len() != 2 || !self.uf.trim().chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(CepServiceError::InvalidInput(format!(
+                "{} is not a valid UF",
+                self.uf
+            )));
+        }
+        if self.city.trim().chars().count() < 3 {
+            return Err(CepServiceError::InvalidInput(
+                "city must have at least 3 characters".to_string(),
+            ));
+        }
+        if self.street.trim().chars().count() < 3 {
+            return Err(CepServiceError::InvalidInput(
+                "street must have at least 3 characters".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,51 +1,263 @@
 use std::{
     collections::HashMap,
-    sync::{Arc, RwLock},
+    future::Future,
+    sync::{Arc, Mutex},
 };
 
-use reqwest::header::{self, HeaderMap};
+use tokio::{
+    sync::OnceCell,
+    time::{sleep, timeout},
+};
+
+use crate::{
+    error::CepServiceError,
+    providers,
+    responses::{cep::CepResponse, error::CepError, service::CepServiceResponse},
+    traits::{cacheable::CepCache, retrievable::Retrievable},
+};
+
+use super::{
+    breaker::{CircuitBreaker, ProviderHealth},
+    cep::Cep,
+    memory_cache::{CacheStats, MemoryCache},
+    policy::ProviderPolicy,
+    search::AddressQuery,
+};
+
+type Flight = Arc<OnceCell<Result<CepResponse, CepServiceError>>>;
+
+#[derive(Debug)]
+struct ProviderSlot {
+    provider: Arc<dyn Retrievable>,
+    policy: ProviderPolicy,
+    breaker: CircuitBreaker,
+}
+
+impl ProviderSlot {
+    fn new(provider: Arc<dyn Retrievable>, policy: ProviderPolicy) -> Self {
+        let breaker = CircuitBreaker::new(policy.breaker_threshold, policy.breaker_cooldown);
+        Self {
+            provider,
+            policy,
+            breaker,
+        }
+    }
+
+    /// Runs an operation against the provider with its timeout and retry policy, feeding the
+    /// outcome to the circuit breaker.
+    async fn call<'a, T, F, Fut>(&'a self, operation: F) -> Result<T, CepServiceError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, CepServiceError>> + 'a,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = match timeout(self.policy.timeout, operation()).await {
+                Ok(result) => result,
+                Err(_) => Err(CepServiceError::Timeout {
+                    provider: self.provider.name(),
+                }),
+            };
+            match result {
+                Err(error) if error.is_provider_failure() => {
+                    if attempt >= self.policy.retries {
+                        self.breaker.record_failure();
+                        return Err(error);
+                    }
+                    attempt += 1;
+                    sleep(self.policy.backoff_for(attempt)).await;
+                }
+                result => {
+                    self.breaker.record_success();
+                    return result;
+                }
+            }
+        }
+    }
 
-use crate::responses::service::CepServiceResponse;
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        self.call(|| self.provider.retrieve(cep)).await
+    }
 
-use super::cep::Cep;
+    async fn search(&self, query: &AddressQuery) -> Result<Vec<CepResponse>, CepServiceError> {
+        self.call(|| self.provider.search(query)).await
+    }
+}
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct CepService {
-    cache: Arc<RwLock<HashMap<String, CepServiceResponse>>>,
+    providers: Arc<Vec<ProviderSlot>>,
+    cache: Arc<MemoryCache>,
+    shared_cache: Option<Arc<dyn CepCache>>,
+    in_flight: Arc<Mutex<HashMap<String, Flight>>>,
 }
 
 impl CepService {
+    /// Builds the service from the comma separated `CEP_PROVIDERS` list, defaulting to ViaCEP.
     pub fn new() -> Self {
+        let providers = providers::build_chain(&providers::names_from_env(), |_| None);
+        Self::with_providers(providers)
+    }
+
+    /// Builds the service from an ordered list of providers, tried first to last, each one
+    /// configured through `ProviderPolicy::from_env`.
+    pub fn with_providers(providers: Vec<Arc<dyn Retrievable>>) -> Self {
+        let providers = providers
+            .into_iter()
+            .map(|provider| {
+                let policy = ProviderPolicy::from_env(provider.name());
+                (provider, policy)
+            })
+            .collect();
+        Self::with_policies(providers)
+    }
+
+    pub fn with_policies(providers: Vec<(Arc<dyn Retrievable>, ProviderPolicy)>) -> Self {
         Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
+            providers: Arc::new(
+                providers
+                    .into_iter()
+                    .map(|(provider, policy)| ProviderSlot::new(provider, policy))
+                    .collect(),
+            ),
+            cache: Arc::new(MemoryCache::from_env()),
+            shared_cache: None,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Adds a second-level cache consulted when the in-process cache misses.
+    pub fn with_cache(mut self, shared_cache: Arc<dyn CepCache>) -> Self {
+        self.shared_cache = Some(shared_cache);
+        self
+    }
+
+    pub fn providers(&self) -> Vec<&'static str> {
+        self.providers
+            .iter()
+            .map(|slot| slot.provider.name())
+            .collect()
+    }
+
+    pub fn health(&self) -> Vec<ProviderHealth> {
+        self.providers
+            .iter()
+            .map(|slot| ProviderHealth {
+                name: slot.provider.name(),
+                state: slot.breaker.state(),
+                consecutive_failures: slot.breaker.consecutive_failures(),
+            })
+            .collect()
+    }
+
+    /// Walks the providers in order, skipping open breakers, until one finds the CEP.
+    async fn retrieve_cep(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        let mut not_found = false;
+        let mut last_error = None;
+        for slot in self.providers.iter() {
+            if !slot.breaker.allows_request() {
+                continue;
+            }
+            match slot.retrieve(cep).await {
+                Ok(address) => return Ok(address),
+                Err(CepServiceError::NotFound) => not_found = true,
+                Err(error @ CepServiceError::InvalidInput(_)) => return Err(error),
+                Err(error) => last_error = Some(error),
+            }
+        }
+        match (not_found, last_error) {
+            (true, _) => Err(CepServiceError::NotFound),
+            (false, Some(error)) => Err(error),
+            (false, None) => Err(CepServiceError::Unavailable),
+        }
+    }
+
+    /// Finds the CEPs of a street, asking the providers that support reverse search in order
+    /// until one of them returns candidates.
+    pub async fn search_by_address(
+        &self,
+        query: &AddressQuery,
+    ) -> Result<Vec<CepResponse>, CepServiceError> {
+        query.validate()?;
+        let mut answered = false;
+        let mut last_error = None;
+        for slot in self.providers.iter() {
+            if !slot.provider.supports_search() || !slot.breaker.allows_request() {
+                continue;
+            }
+            match slot.search(query).await {
+                Ok(candidates) if !candidates.is_empty() => return Ok(candidates),
+                Ok(_) => answered = true,
+                Err(error @ CepServiceError::InvalidInput(_)) => return Err(error),
+                Err(error) => last_error = Some(error),
+            }
+        }
+        match (answered, last_error) {
+            (true, _) => Ok(Vec::new()),
+            (false, Some(error)) => Err(error),
+            (false, None) => Err(CepServiceError::Unavailable),
         }
     }
 
-    async fn retrieve_cep(cep: &Cep) -> CepServiceResponse {
-        let mut headers = HeaderMap::new();
-        headers.insert(header::ACCEPT, "application/json".parse().unwrap());
-        let client = reqwest::Client::new();
-        let url = format!("https://viacep.com.br/ws/{}/json/", cep);
-        let response = client.get(&url).headers(headers).send().await.unwrap();
-        let json = response.text().await.unwrap();
-        serde_json::from_str::<CepServiceResponse>(&json).unwrap()
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
     }
 
-    pub async fn get_address(&self, cep: Cep) -> CepServiceResponse {
+    pub async fn get_address(&self, cep: Cep) -> Result<CepResponse, CepServiceError> {
+        let key = cep.digits();
+
         // Try to read from cache first
+        if let Some(response) = self.cache.get(&key) {
+            return response.into_result();
+        }
+
+        // Concurrent lookups of the same CEP share a single resolution
+        let flight = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight.entry(key.clone()).or_default().clone()
+        };
+        let result = flight.get_or_init(|| self.resolve(&cep)).await.clone();
         {
-            let cache_read = self.cache.read().unwrap();
-            if let Some(response) = cache_read.get(&cep.to_string()) {
-                return response.clone();
+            let mut in_flight = self.in_flight.lock().unwrap();
+            if in_flight
+                .get(&key)
+                .is_some_and(|current| Arc::ptr_eq(current, &flight))
+            {
+                in_flight.remove(&key);
             }
         }
+        result
+    }
 
-        // If not found in cache, retrieve and store the response
-        let response = Self::retrieve_cep(&cep).await;
-        {
-            let mut cache_write = self.cache.write().unwrap();
-            cache_write.insert(cep.to_string(), response.clone());
+    /// Resolves a CEP missing from the in-process cache through the shared cache, then the
+    /// providers, storing the answer in both caches. Provider failures are not cached.
+    async fn resolve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        if let Some(shared_cache) = &self.shared_cache {
+            if let Some(response) = shared_cache.get(cep).await {
+                self.cache.insert(cep.digits(), response.clone());
+                return response.into_result();
+            }
+        }
+
+        let result = self.retrieve_cep(cep).await;
+        let response = match &result {
+            Ok(address) => CepServiceResponse::CepFound(address.clone()),
+            Err(CepServiceError::NotFound) => CepServiceResponse::CepNotFound(CepError {
+                erro: true,
+                mensagem: None,
+            }),
+            Err(_) => return result,
+        };
+        if let Some(shared_cache) = &self.shared_cache {
+            shared_cache.set(cep, &response).await;
         }
-        response
+        self.cache.insert(cep.digits(), response);
+        result
+    }
+}
+
+impl Default for CepService {
+    fn default() -> Self {
+        Self::new()
     }
 }
@@ -1 +1,2 @@
+pub mod cacheable;
 pub mod retrievable;
@@ -0,0 +1,16 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use crate::{responses::service::CepServiceResponse, structs::cep::Cep};
+
+/// A shared, second-level store for CEP lookups, consulted after the in-process cache.
+///
+/// Implementations are best-effort: a cache that cannot be reached behaves as a miss and never
+/// fails the lookup itself.
+#[async_trait]
+pub trait CepCache: Debug + Send + Sync {
+    async fn get(&self, cep: &Cep) -> Option<CepServiceResponse>;
+
+    async fn set(&self, cep: &Cep, response: &CepServiceResponse);
+}
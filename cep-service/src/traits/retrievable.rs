@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use crate::{
+    error::CepServiceError,
+    responses::cep::CepResponse,
+    structs::{cep::Cep, search::AddressQuery},
+};
+
+/// A source able to resolve a CEP into an address.
+///
+/// Provider failures (see `CepServiceError::is_provider_failure`) count against the provider's
+/// circuit breaker; `NotFound` and `InvalidInput` are regular answers.
+#[async_trait]
+pub trait Retrievable: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError>;
+
+    /// Whether the provider implements `search`; providers that do not are skipped.
+    fn supports_search(&self) -> bool {
+        false
+    }
+
+    /// Lists the CEPs matching a street in a city.
+    async fn search(&self, _query: &AddressQuery) -> Result<Vec<CepResponse>, CepServiceError> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,251 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use cep_service::structs::cep::Cep;
+
+/// Score from which a new person is refused as a duplicate unless explicitly allowed.
+pub const LIKELY_DUPLICATE_SCORE: f64 = 0.8;
+/// Score from which a person is listed as a possible duplicate.
+pub const POSSIBLE_DUPLICATE_SCORE: f64 = 0.5;
+
+const NAME_WEIGHT: f64 = 0.4;
+const BIRTH_DATE_WEIGHT: f64 = 0.3;
+const MOTHERS_NAME_WEIGHT: f64 = 0.2;
+const CEP_WEIGHT: f64 = 0.1;
+
+/// Particles that carry no identity in Brazilian names ("João da Silva").
+const PARTICLES: &[&str] = &["DA", "DAS", "DE", "DI", "DO", "DOS", "DU", "E", "Y"];
+
+/// Digraphs and letters rewritten to a common sound, in order, after BuscaBR.
+const REPLACEMENTS: &[(&str, &str)] = &[
+    ("BL", "B"),
+    ("BR", "B"),
+    ("PH", "F"),
+    ("GL", "G"),
+    ("GR", "G"),
+    ("MG", "G"),
+    ("NG", "G"),
+    ("RG", "G"),
+    ("GE", "J"),
+    ("GI", "J"),
+    ("RJ", "J"),
+    ("MJ", "J"),
+    ("NJ", "J"),
+    ("CE", "S"),
+    ("CI", "S"),
+    ("CH", "S"),
+    ("CS", "S"),
+    ("CT", "T"),
+    ("Q", "K"),
+    ("C", "K"),
+    ("LH", "L"),
+    ("NH", "N"),
+    ("RM", "M"),
+    ("GM", "M"),
+    ("MD", "M"),
+    ("SM", "M"),
+    ("AO", "M"),
+    ("PR", "P"),
+    ("ST", "T"),
+    ("TR", "T"),
+    ("TL", "T"),
+    ("LT", "T"),
+    ("RT", "T"),
+    ("W", "V"),
+    ("X", "S"),
+    ("Y", "I"),
+    ("Z", "S"),
+    ("H", ""),
+];
+
+/// Uppercases and drops diacritics, keeping only ASCII letters and spaces.
+fn fold(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' | 'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+            'é' | 'è' | 'ê' | 'ë' | 'É' | 'È' | 'Ê' | 'Ë' => 'E',
+            'í' | 'ì' | 'î' | 'ï' | 'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' | 'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+            'ú' | 'ù' | 'û' | 'ü' | 'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+            'ç' | 'Ç' => 'C',
+            'ñ' | 'Ñ' => 'N',
+            c if c.is_ascii_alphabetic() => c.to_ascii_uppercase(),
+            _ => ' ',
+        })
+        .collect()
+}
+
+fn word_key(word: &str) -> String {
+    let mut word = word.to_string();
+    for (from, to) in REPLACEMENTS {
+        word = word.replace(from, to);
+    }
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return word;
+    };
+    // Final S, R and L are often dropped or swapped in speech ("Luiz"/"Luis", "Raquel").
+    let rest: String = chars.filter(|c| !"AEIOU".contains(*c)).collect();
+    let rest = rest.trim_end_matches(['S', 'R', 'L']);
+    let mut key = String::from(first);
+    for c in rest.chars() {
+        if !key.ends_with(c) {
+            key.push(c);
+        }
+    }
+    key
+}
+
+/// Brazilian-Portuguese phonetic key of a full name, one code per word, so that
+/// "João da Silva" and "Joao Sylva" share the key `JM SLV`.
+pub fn phonetic_key(name: &str) -> String {
+    fold(name)
+        .split_whitespace()
+        .filter(|word| !PARTICLES.contains(word))
+        .map(word_key)
+        .filter(|key| !key.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Share of words two phonetic keys have in common, from 0 to 1.
+fn key_similarity(left: &str, right: &str) -> f64 {
+    let left: Vec<&str> = left.split(' ').filter(|word| !word.is_empty()).collect();
+    let right: Vec<&str> = right.split(' ').filter(|word| !word.is_empty()).collect();
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+    let shared = left.iter().filter(|word| right.contains(word)).count();
+    shared as f64 / left.len().max(right.len()) as f64
+}
+
+/// The fields duplicate detection compares.
+#[derive(Debug, Clone)]
+pub struct Identity<'a> {
+    pub name: &'a str,
+    pub mothers_name: &'a str,
+    pub birth_date: NaiveDate,
+    pub cep: Option<Cep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateScore {
+    pub score: f64,
+    pub name: f64,
+    pub mothers_name: f64,
+    pub birth_date: bool,
+    pub cep: bool,
+}
+
+impl DuplicateScore {
+    pub fn between(left: &Identity, right: &Identity) -> Self {
+        let name = key_similarity(&phonetic_key(left.name), &phonetic_key(right.name));
+        let mothers_name = key_similarity(
+            &phonetic_key(left.mothers_name),
+            &phonetic_key(right.mothers_name),
+        );
+        let birth_date = left.birth_date == right.birth_date;
+        let cep = left.cep.is_some() && left.cep == right.cep;
+        let score = name * NAME_WEIGHT
+            + mothers_name * MOTHERS_NAME_WEIGHT
+            + if birth_date { BIRTH_DATE_WEIGHT } else { 0.0 }
+            + if cep { CEP_WEIGHT } else { 0.0 };
+        Self {
+            score: (score * 100.0).round() / 100.0,
+            name,
+            mothers_name,
+            birth_date,
+            cep,
+        }
+    }
+
+    pub fn is_likely(&self) -> bool {
+        self.score >= LIKELY_DUPLICATE_SCORE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same_key(left: &str, right: &str) {
+        assert_eq!(
+            phonetic_key(left),
+            phonetic_key(right),
+            "{} and {} should share a key",
+            left,
+            right
+        );
+    }
+
+    #[test]
+    fn documented_example() {
+        assert_eq!(phonetic_key("João da Silva"), "JM SLV");
+        same_key("João da Silva", "Joao Sylva");
+    }
+
+    #[test]
+    fn ignores_accents_and_case() {
+        same_key("José Antônio", "jose antonio");
+        same_key("Conceição", "CONCEICAO");
+        same_key("Ângela Lúcia", "Angela Lucia");
+        same_key("Muñoz", "Munoz");
+    }
+
+    #[test]
+    fn drops_silent_h() {
+        same_key("Helena", "Elena");
+        same_key("Thiago", "Tiago");
+        same_key("Hugo", "Ugo");
+    }
+
+    #[test]
+    fn merges_portuguese_consonant_substitutions() {
+        same_key("Luiz", "Luis");
+        same_key("Sousa", "Souza");
+        same_key("Raphael", "Rafael");
+        same_key("Walter", "Valter");
+        same_key("Cintia", "Sintia");
+        same_key("Kátia", "Catia");
+        same_key("Queiroz", "Keiroz");
+        same_key("Gerson", "Jerson");
+        same_key("Ximenes", "Chimenes");
+        same_key("Yara", "Iara");
+    }
+
+    #[test]
+    fn keeps_distinct_names_apart() {
+        assert_ne!(phonetic_key("Maria"), phonetic_key("Mario Pereira"));
+        assert_ne!(phonetic_key("Paulo"), phonetic_key("Pedro"));
+    }
+
+    #[test]
+    fn skips_particles_and_punctuation() {
+        same_key("Maria dos Santos", "Maria Santos");
+        same_key("Ana de Souza e Silva", "Ana Souza Silva");
+        same_key("D'Ávila", "D Avila");
+        assert_eq!(phonetic_key("da de do"), "");
+    }
+
+    #[test]
+    fn scores_identical_people_as_likely_duplicates() {
+        let birth_date = NaiveDate::from_ymd_opt(1990, 5, 17).unwrap();
+        let cep = "01001-000".parse::<Cep>().ok();
+        let left = Identity {
+            name: "Luiz Souza",
+            mothers_name: "Helena Souza",
+            birth_date,
+            cep,
+        };
+        let right = Identity {
+            name: "Luís Sousa",
+            mothers_name: "Elena Sousa",
+            birth_date,
+            cep,
+        };
+        let score = DuplicateScore::between(&left, &right);
+        assert_eq!(score.score, 1.0);
+        assert!(score.is_likely());
+    }
+}
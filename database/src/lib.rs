@@ -1,3 +1,6 @@
+pub mod duplicates;
 pub mod models;
+pub mod pagination;
 pub mod pool;
+pub mod providers;
 pub mod traits;
@@ -1,3 +1,9 @@
-pub mod user;
-pub mod person;
+pub mod address;
 pub mod annotation;
+pub mod annotation_revision;
+pub mod audit_event;
+pub mod cep_address;
+pub mod person;
+pub mod person_merge;
+pub mod person_version;
+pub mod user;
@@ -0,0 +1,531 @@
+use async_trait::async_trait;
+use cep_service::structs::cep::Cep;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{error::BoxDynError, FromRow, MySql, MySqlConnection, MySqlPool, QueryBuilder};
+
+use crate::{
+    models::{
+        audit_event::AuditContext,
+        person::{PersonModel, ResolvedAddress},
+    },
+    traits::{audit::Audit, database::Database, persist::Persist},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum AddressType {
+    #[default]
+    Residential,
+    Work,
+    Mailing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AddressModel {
+    pub id: u64,
+    pub person_id: u64,
+    #[sqlx(rename = "type")]
+    #[serde(rename = "type")]
+    pub address_type: AddressType,
+    pub cep: Cep,
+    pub number: String,
+    pub complement: String,
+    pub is_primary: bool,
+    #[serde(default)]
+    pub version: u32,
+    #[sqlx(flatten)]
+    pub resolved: ResolvedAddress,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// An `addresses` row as the `query_as!` macros read it, which cannot fill a flattened field.
+struct AddressRow {
+    id: u64,
+    person_id: u64,
+    address_type: AddressType,
+    cep: Cep,
+    number: String,
+    complement: String,
+    is_primary: bool,
+    version: u32,
+    logradouro: String,
+    complemento: String,
+    bairro: String,
+    localidade: String,
+    uf: String,
+    ibge: String,
+    gia: String,
+    ddd: String,
+    siafi: String,
+    address_verified_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<AddressRow> for AddressModel {
+    fn from(row: AddressRow) -> Self {
+        Self {
+            id: row.id,
+            person_id: row.person_id,
+            address_type: row.address_type,
+            cep: row.cep,
+            number: row.number,
+            complement: row.complement,
+            is_primary: row.is_primary,
+            version: row.version,
+            resolved: ResolvedAddress {
+                logradouro: row.logradouro,
+                complemento: row.complemento,
+                bairro: row.bairro,
+                localidade: row.localidade,
+                uf: row.uf,
+                ibge: row.ibge,
+                gia: row.gia,
+                ddd: row.ddd,
+                siafi: row.siafi,
+                address_verified_at: row.address_verified_at,
+            },
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewAddressModel {
+    #[serde(rename = "type", default)]
+    pub address_type: AddressType,
+    pub cep: Cep,
+    #[serde(default)]
+    pub number: String,
+    #[serde(default)]
+    pub complement: String,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+impl AddressModel {
+    pub fn new(person_id: u64, address: NewAddressModel, resolved: ResolvedAddress) -> Self {
+        Self {
+            id: 0,
+            person_id,
+            address_type: address.address_type,
+            cep: address.cep,
+            number: address.number,
+            complement: address.complement,
+            is_primary: address.is_primary,
+            version: 1,
+            resolved,
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    pub async fn list_by_person(person_id: u64, connection: &MySqlPool) -> sqlx::Result<Vec<Self>> {
+        let addresses = sqlx::query_as!(
+            AddressRow,
+            r#"
+                SELECT id, person_id, type as "address_type: AddressType", cep as "cep: Cep", number, complement, is_primary as "is_primary: bool", version, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at, updated_at
+                FROM addresses
+                WHERE person_id = ?
+                ORDER BY is_primary DESC, id
+            "#,
+            person_id
+        )
+        .fetch_all(connection)
+        .await?;
+        Ok(addresses.into_iter().map(AddressModel::from).collect())
+    }
+
+    /// Addresses of every person in `person_ids`, fetched in a single query.
+    pub async fn list_by_persons(
+        person_ids: &[u64],
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<Self>> {
+        if person_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = QueryBuilder::<MySql>::new("SELECT * FROM addresses WHERE person_id IN (");
+        let mut ids = query.separated(", ");
+        for id in person_ids {
+            ids.push_bind(*id);
+        }
+        query.push(") ORDER BY person_id, is_primary DESC, id");
+        query
+            .build_query_as::<AddressModel>()
+            .fetch_all(connection)
+            .await
+    }
+
+    pub async fn get_by_person(
+        person_id: u64,
+        id: u64,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            AddressRow,
+            r#"
+                SELECT id, person_id, type as "address_type: AddressType", cep as "cep: Cep", number, complement, is_primary as "is_primary: bool", version, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at, updated_at
+                FROM addresses
+                WHERE id = ? AND person_id = ?
+            "#,
+            id,
+            person_id
+        )
+        .fetch_one(connection)
+        .await
+        .map(AddressModel::from)
+    }
+
+    pub async fn get_primary(
+        person_id: u64,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Option<Self>> {
+        let primary = sqlx::query_as!(
+            AddressRow,
+            r#"
+                SELECT id, person_id, type as "address_type: AddressType", cep as "cep: Cep", number, complement, is_primary as "is_primary: bool", version, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at, updated_at
+                FROM addresses
+                WHERE person_id = ? AND is_primary
+                LIMIT 1
+            "#,
+            person_id
+        )
+        .fetch_optional(connection)
+        .await?;
+        Ok(primary.map(AddressModel::from))
+    }
+
+    /// Addresses never resolved or last verified before `verified_before`, oldest first.
+    pub async fn list_stale(
+        verified_before: DateTime<Utc>,
+        limit: u32,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<Self>> {
+        let addresses = sqlx::query_as!(
+            AddressRow,
+            r#"
+                SELECT addresses.id, addresses.person_id, addresses.type as "address_type: AddressType", addresses.cep as "cep: Cep", addresses.number, addresses.complement, addresses.is_primary as "is_primary: bool", addresses.version, addresses.logradouro, addresses.complemento, addresses.bairro, addresses.localidade, addresses.uf, addresses.ibge, addresses.gia, addresses.ddd, addresses.siafi, addresses.address_verified_at, addresses.created_at, addresses.updated_at
+                FROM addresses
+                JOIN persons ON persons.id = addresses.person_id AND persons.deleted_at IS NULL
+                WHERE addresses.address_verified_at IS NULL OR addresses.address_verified_at < ?
+                ORDER BY addresses.address_verified_at IS NOT NULL, addresses.address_verified_at
+                LIMIT ?
+            "#,
+            verified_before,
+            limit
+        )
+        .fetch_all(connection)
+        .await?;
+        Ok(addresses.into_iter().map(AddressModel::from).collect())
+    }
+
+    /// Inserts the address; a primary one is mirrored on the person as a version attributed to
+    /// `editor_id`.
+    pub async fn insert_by(
+        &self,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        let is_primary = self.is_primary || !Self::has_primary(self.person_id, connection).await?;
+        let result = sqlx::query!(
+            r#"
+                INSERT INTO addresses (person_id, type, cep, number, complement, is_primary, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at)
+                VALUES (?, ?, ?, ?, ?, false, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            self.person_id,
+            self.address_type,
+            self.cep,
+            &self.number,
+            &self.complement,
+            &self.resolved.logradouro,
+            &self.resolved.complemento,
+            &self.resolved.bairro,
+            &self.resolved.localidade,
+            &self.resolved.uf,
+            &self.resolved.ibge,
+            &self.resolved.gia,
+            &self.resolved.ddd,
+            &self.resolved.siafi,
+            self.resolved.address_verified_at,
+            self.created_at,
+        )
+        .execute(&mut *connection)
+        .await?;
+        let id = result.last_insert_id();
+        if is_primary {
+            self.make_primary(id, editor_id, connection).await?;
+        }
+        Self::get_in(id, connection).await
+    }
+
+    /// Updates the address, attributing a change of the primary one to `editor_id`. Fails with
+    /// `RowNotFound` when the stored address is no longer at `self.version`.
+    pub async fn update_by(
+        &self,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE addresses
+                SET type = ?, cep = ?, number = ?, complement = ?, logradouro = ?, complemento = ?, bairro = ?, localidade = ?, uf = ?, ibge = ?, gia = ?, ddd = ?, siafi = ?, address_verified_at = ?, version = version + 1
+                WHERE id = ? AND version = ?
+            "#,
+            self.address_type,
+            self.cep,
+            &self.number,
+            &self.complement,
+            &self.resolved.logradouro,
+            &self.resolved.complemento,
+            &self.resolved.bairro,
+            &self.resolved.localidade,
+            &self.resolved.uf,
+            &self.resolved.ibge,
+            &self.resolved.gia,
+            &self.resolved.ddd,
+            &self.resolved.siafi,
+            self.resolved.address_verified_at,
+            self.id,
+            self.version,
+        )
+        .execute(&mut *connection)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        // A person always keeps one primary address, so the flag can only be moved, not cleared.
+        if self.is_primary {
+            self.make_primary(self.id, editor_id, connection).await?;
+        }
+        Self::get_in(self.id, connection).await
+    }
+
+    /// Deletes the address, handing the primary flag to the oldest remaining one. Fails with
+    /// `RowNotFound` when the stored address is no longer at `self.version`.
+    pub async fn delete_by(
+        &self,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> Result<(), BoxDynError> {
+        let result = sqlx::query!(
+            "DELETE FROM addresses WHERE id = ? AND version = ?",
+            self.id,
+            self.version,
+        )
+        .execute(&mut *connection)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        if self.is_primary {
+            let next = sqlx::query_as!(
+                AddressRow,
+                r#"
+                    SELECT id, person_id, type as "address_type: AddressType", cep as "cep: Cep", number, complement, is_primary as "is_primary: bool", version, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at, updated_at
+                    FROM addresses
+                    WHERE person_id = ?
+                    ORDER BY id
+                    LIMIT 1
+                "#,
+                self.person_id
+            )
+            .fetch_optional(&mut *connection)
+            .await?
+            .map(AddressModel::from);
+            match next {
+                Some(next) => next.make_primary(next.id, editor_id, connection).await?,
+                None => Self::clear_primary(self.person_id, editor_id, connection).await?,
+            }
+        }
+        PersonModel::touch(self.person_id, connection).await?;
+        Ok(())
+    }
+
+    /// Stores a freshly resolved address, mirroring it on the person as a new version when it is
+    /// the primary one. The new `address_verified_at` moves `updated_at` forward through its
+    /// `on update` default.
+    pub async fn update_resolved(
+        &self,
+        resolved: &ResolvedAddress,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<()> {
+        let mut transaction = connection.begin().await?;
+        sqlx::query!(
+            r#"
+                UPDATE addresses
+                SET logradouro = ?, complemento = ?, bairro = ?, localidade = ?, uf = ?, ibge = ?, gia = ?, ddd = ?, siafi = ?, address_verified_at = ?, version = version + 1
+                WHERE id = ?
+            "#,
+            &resolved.logradouro,
+            &resolved.complemento,
+            &resolved.bairro,
+            &resolved.localidade,
+            &resolved.uf,
+            &resolved.ibge,
+            &resolved.gia,
+            &resolved.ddd,
+            &resolved.siafi,
+            resolved.address_verified_at,
+            self.id,
+        )
+        .execute(&mut *transaction)
+        .await?;
+        if self.is_primary {
+            PersonModel::update_address(self.person_id, resolved, None, &mut transaction).await?;
+        }
+        transaction.commit().await
+    }
+
+    /// Makes `id` the only primary address of the person and mirrors it on the persons row,
+    /// which still carries the primary CEP and its resolved address.
+    async fn make_primary(
+        &self,
+        id: u64,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE addresses
+                SET version = version + (is_primary <> (id = ?)), is_primary = (id = ?)
+                WHERE person_id = ?
+            "#,
+            id,
+            id,
+            self.person_id,
+        )
+        .execute(&mut *connection)
+        .await?;
+        PersonModel::mirror_primary(
+            self.person_id,
+            Some(self.cep),
+            &self.resolved,
+            editor_id,
+            connection,
+        )
+        .await
+    }
+
+    /// Empties the CEP and address mirrored on the persons row once the person has no address
+    /// left, so that it no longer describes a deleted one.
+    async fn clear_primary(
+        person_id: u64,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<()> {
+        PersonModel::mirror_primary(
+            person_id,
+            None,
+            &ResolvedAddress::default(),
+            editor_id,
+            connection,
+        )
+        .await
+    }
+
+    async fn has_primary(person_id: u64, connection: &mut MySqlConnection) -> sqlx::Result<bool> {
+        let primary = sqlx::query_scalar!(
+            "SELECT id FROM addresses WHERE person_id = ? AND is_primary LIMIT 1 FOR UPDATE",
+            person_id
+        )
+        .fetch_optional(&mut *connection)
+        .await?;
+        Ok(primary.is_some())
+    }
+}
+
+#[async_trait]
+impl Database<MySqlPool> for AddressModel {
+    type Connection = MySqlPool;
+    type Model = Self;
+
+    async fn get<'long>(id: u64, connection: &'long Self::Connection) -> sqlx::Result<Self>
+    where
+        Self: 'long,
+    {
+        Self::get_in(id, &mut *connection.acquire().await?).await
+    }
+
+    async fn list<'long>(connection: &'long Self::Connection) -> sqlx::Result<Vec<Self>>
+    where
+        Self: 'long,
+    {
+        let addresses = sqlx::query_as!(
+            AddressRow,
+            r#"
+                SELECT id, person_id, type as "address_type: AddressType", cep as "cep: Cep", number, complement, is_primary as "is_primary: bool", version, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at, updated_at
+                FROM addresses
+            "#
+        )
+        .fetch_all(connection)
+        .await?;
+        Ok(addresses.into_iter().map(AddressModel::from).collect())
+    }
+}
+
+#[async_trait]
+impl Persist for AddressModel {
+    async fn get_in(id: u64, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            AddressRow,
+            r#"
+                SELECT id, person_id, type as "address_type: AddressType", cep as "cep: Cep", number, complement, is_primary as "is_primary: bool", version, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, created_at, updated_at
+                FROM addresses
+                WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map(AddressModel::from)
+    }
+
+    async fn insert_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        self.insert_by(None, connection).await
+    }
+
+    async fn update_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        self.update_by(None, connection).await
+    }
+
+    async fn delete_in(&self, connection: &mut MySqlConnection) -> Result<(), BoxDynError> {
+        self.delete_by(None, connection).await
+    }
+}
+
+#[async_trait]
+impl Audit for AddressModel {
+    const ENTITY: &'static str = "address";
+
+    fn entity_id(&self) -> u64 {
+        self.id
+    }
+
+    async fn write_insert(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.insert_by(audit.actor_id, connection).await
+    }
+
+    async fn write_update(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.update_by(audit.actor_id, connection).await
+    }
+
+    async fn write_delete(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> Result<(), BoxDynError> {
+        self.delete_by(audit.actor_id, connection).await
+    }
+}
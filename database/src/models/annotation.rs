@@ -1,25 +1,73 @@
-use serde::{Deserialize, Serialize};
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{MySqlPool, FromRow};
+use serde::{Deserialize, Serialize};
+use sqlx::{error::BoxDynError, FromRow, MySql, MySqlConnection, MySqlPool, QueryBuilder};
+
+use crate::{
+    models::{audit_event::AuditContext, person::PersonModel},
+    traits::{audit::Audit, database::Database, persist::Persist, soft_delete::SoftDelete},
+};
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct AnnotationModel {
     pub id: u64,
     pub person_id: u64,
+    pub author_id: Option<u64>,
     pub title: String,
     pub description: String,
+    pub version: u32,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewAnnotationModel {
+    pub title: String,
+    pub description: String,
+}
+
+impl From<&AnnotationModel> for NewAnnotationModel {
+    fn from(annotation: &AnnotationModel) -> Self {
+        Self {
+            title: annotation.title.clone(),
+            description: annotation.description.clone(),
+        }
+    }
+}
+
 impl AnnotationModel {
-    pub async fn list(person_id: u64, connection: &MySqlPool) -> Result<Vec<AnnotationModel>, sqlx::Error> {
+    pub fn new(person_id: u64, author_id: u64, annotation: NewAnnotationModel) -> Self {
+        Self {
+            id: 0,
+            person_id,
+            author_id: Some(author_id),
+            title: annotation.title,
+            description: annotation.description,
+            version: 1,
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    pub fn with_changes(self, changes: NewAnnotationModel) -> Self {
+        Self {
+            title: changes.title,
+            description: changes.description,
+            updated_at: Some(Utc::now()),
+            ..self
+        }
+    }
+
+    pub async fn list_by_person(
+        person_id: u64,
+        connection: &MySqlPool,
+    ) -> Result<Vec<AnnotationModel>, sqlx::Error> {
         let annotations = sqlx::query_as!(
             AnnotationModel,
             r#"
-            SELECT id, person_id, title, description, created_at, updated_at
-            FROM annotations
-            WHERE person_id = ?
+                SELECT id, person_id, author_id, title, description, version, created_at, updated_at
+                FROM annotations
+                WHERE person_id = ? AND deleted_at IS NULL
             "#,
             person_id
         )
@@ -27,4 +75,214 @@ impl AnnotationModel {
         .await?;
         Ok(annotations)
     }
+
+    /// Updates the annotation, first keeping its previous title and description as a revision
+    /// attributed to `editor_id`. Fails with `RowNotFound` when the stored annotation is no
+    /// longer at `self.version`.
+    pub async fn update_by(
+        &self,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        sqlx::query!(
+            r#"
+                INSERT INTO annotation_revisions (annotation_id, editor_id, title, description)
+                SELECT id, ?, title, description FROM annotations WHERE id = ?
+            "#,
+            editor_id,
+            self.id,
+        )
+        .execute(&mut *connection)
+        .await?;
+        let result = sqlx::query!(
+            r#"
+                UPDATE annotations
+                SET title = ?, description = ?, updated_at = ?, version = version + 1
+                WHERE id = ? AND version = ?
+            "#,
+            &self.title,
+            &self.description,
+            self.updated_at,
+            self.id,
+            self.version,
+        )
+        .execute(&mut *connection)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Self::get_in(self.id, connection).await
+    }
+
+    /// Annotations of every person in `person_ids`, fetched in a single query.
+    pub async fn list_by_persons(
+        person_ids: &[u64],
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<Self>> {
+        if person_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query =
+            QueryBuilder::<MySql>::new("SELECT * FROM annotations WHERE person_id IN (");
+        let mut ids = query.separated(", ");
+        for id in person_ids {
+            ids.push_bind(*id);
+        }
+        query.push(") AND deleted_at IS NULL ORDER BY person_id, id");
+        query
+            .build_query_as::<AnnotationModel>()
+            .fetch_all(connection)
+            .await
+    }
+
+    pub async fn get_by_person(
+        person_id: u64,
+        id: u64,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            AnnotationModel,
+            r#"
+                SELECT id, person_id, author_id, title, description, version, created_at, updated_at
+                FROM annotations
+                WHERE id = ? AND person_id = ? AND deleted_at IS NULL
+            "#,
+            id,
+            person_id
+        )
+        .fetch_one(connection)
+        .await
+    }
+
+    /// Like `get_by_person`, but also finds soft-deleted annotations, for restoring.
+    pub async fn get_with_deleted(
+        person_id: u64,
+        id: u64,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            AnnotationModel,
+            r#"
+                SELECT id, person_id, author_id, title, description, version, created_at, updated_at
+                FROM annotations
+                WHERE id = ? AND person_id = ?
+            "#,
+            id,
+            person_id
+        )
+        .fetch_one(connection)
+        .await
+    }
+}
+
+#[async_trait]
+impl Database<MySqlPool> for AnnotationModel {
+    type Connection = MySqlPool;
+    type Model = Self;
+
+    async fn get<'long>(id: u64, connection: &'long Self::Connection) -> sqlx::Result<Self>
+    where
+        Self: 'long,
+    {
+        Self::get_in(id, &mut *connection.acquire().await?).await
+    }
+
+    async fn list<'long>(connection: &'long Self::Connection) -> sqlx::Result<Vec<Self>>
+    where
+        Self: 'long,
+    {
+        sqlx::query_as!(
+            AnnotationModel,
+            r#"
+                SELECT id, person_id, author_id, title, description, version, created_at, updated_at
+                FROM annotations
+                WHERE deleted_at IS NULL
+            "#
+        )
+        .fetch_all(connection)
+        .await
+    }
+}
+
+#[async_trait]
+impl Persist for AnnotationModel {
+    async fn get_in(id: u64, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            AnnotationModel,
+            r#"
+                SELECT id, person_id, author_id, title, description, version, created_at, updated_at
+                FROM annotations
+                WHERE id = ? AND deleted_at IS NULL
+            "#,
+            id
+        )
+        .fetch_one(connection)
+        .await
+    }
+
+    async fn insert_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        let result = sqlx::query!(
+            r#"
+                INSERT INTO annotations (person_id, author_id, title, description, created_at)
+                VALUES (?, ?, ?, ?, ?)
+            "#,
+            self.person_id,
+            self.author_id,
+            &self.title,
+            &self.description,
+            self.created_at,
+        )
+        .execute(&mut *connection)
+        .await?;
+        Self::get_in(result.last_insert_id(), connection).await
+    }
+
+    async fn update_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        self.update_by(None, connection).await
+    }
+
+    async fn delete_in(&self, connection: &mut MySqlConnection) -> Result<(), BoxDynError> {
+        let result = sqlx::query!("DELETE FROM annotations WHERE id = ?", self.id)
+            .execute(&mut *connection)
+            .await?;
+        if result.rows_affected() != 1 {
+            return Err("Error deleting annotation".into());
+        }
+        PersonModel::touch(self.person_id, connection).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SoftDelete for AnnotationModel {
+    const TABLE: &'static str = "annotations";
+
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    async fn after_soft_delete(&self, connection: &mut MySqlConnection) -> sqlx::Result<()> {
+        PersonModel::touch(self.person_id, connection).await
+    }
+}
+
+#[async_trait]
+impl Audit for AnnotationModel {
+    const ENTITY: &'static str = "annotation";
+
+    fn entity_id(&self) -> u64 {
+        self.id
+    }
+
+    async fn write_update(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.update_by(audit.actor_id, connection).await
+    }
 }
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, MySqlPool};
+
+/// A previous title/description of an annotation, recorded when `editor_id` replaced it.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AnnotationRevisionModel {
+    pub id: u64,
+    pub annotation_id: u64,
+    pub editor_id: Option<u64>,
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AnnotationRevisionModel {
+    pub async fn list_by_annotation(
+        annotation_id: u64,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as::<_, AnnotationRevisionModel>(
+            "SELECT * FROM annotation_revisions WHERE annotation_id = ? ORDER BY id DESC",
+        )
+        .bind(annotation_id)
+        .fetch_all(connection)
+        .await
+    }
+}
@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, Executor, FromRow, MySql, MySqlPool};
+
+use crate::{
+    pagination::{fetch_page, Page, PageRequest, SortValue},
+    traits::{database::Database, paginate::Paginate},
+};
+
+/// Fields left out of diffs, since every write touches them.
+const UNDIFFED_FIELDS: &[&str] = &["updated_at"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum AuditAction {
+    Created,
+    Updated,
+    /// Soft deleted, still restorable.
+    Deleted,
+    Restored,
+    /// Removed from the database for good.
+    Purged,
+    Merged,
+}
+
+/// Who is making a change and in which request, as set by the request id and auth middlewares.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor_id: Option<u64>,
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AuditEventModel {
+    pub id: u64,
+    pub actor_id: Option<u64>,
+    pub entity_type: String,
+    pub entity_id: u64,
+    pub action: AuditAction,
+    /// Changed fields as they were before the action, empty for creations.
+    pub old_values: Option<Json<Value>>,
+    /// Changed fields as they are after the action, empty for deletions.
+    pub new_values: Option<Json<Value>>,
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Narrows two JSON documents down to the top-level fields that differ between them.
+pub fn diff(before: Value, after: Value) -> (Value, Value) {
+    match (before, after) {
+        (Value::Object(mut before), Value::Object(mut after)) => {
+            let changed: Vec<String> = before
+                .keys()
+                .chain(after.keys())
+                .filter(|key| !UNDIFFED_FIELDS.contains(&key.as_str()))
+                .filter(|key| before.get(*key) != after.get(*key))
+                .cloned()
+                .collect();
+            before.retain(|key, _| changed.contains(key));
+            after.retain(|key, _| changed.contains(key));
+            (Value::Object(before), Value::Object(after))
+        }
+        (before, after) => (before, after),
+    }
+}
+
+impl AuditEventModel {
+    /// Writes an event. When both sides are given only the fields that changed are kept, and
+    /// an update that changed nothing is not written.
+    pub async fn record<'e, E>(
+        audit: &AuditContext,
+        entity_type: &str,
+        entity_id: u64,
+        action: AuditAction,
+        old_values: Option<Value>,
+        new_values: Option<Value>,
+        executor: E,
+    ) -> sqlx::Result<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        let (old_values, new_values) = match (old_values, new_values) {
+            (Some(before), Some(after)) => {
+                let (before, after) = diff(before, after);
+                if action == AuditAction::Updated && before == after {
+                    return Ok(());
+                }
+                (Some(before), Some(after))
+            }
+            values => values,
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO audit_events (actor_id, entity_type, entity_id, action, old_values, new_values, request_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(audit.actor_id)
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(action)
+        .bind(old_values.map(Json))
+        .bind(new_values.map(Json))
+        .bind(&audit.request_id)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Database<MySqlPool> for AuditEventModel {
+    type Connection = MySqlPool;
+    type Model = Self;
+
+    async fn get<'long>(id: u64, connection: &'long Self::Connection) -> sqlx::Result<Self>
+    where
+        Self: 'long,
+    {
+        sqlx::query_as::<_, AuditEventModel>("SELECT * FROM audit_events WHERE id = ?")
+            .bind(id)
+            .fetch_one(connection)
+            .await
+    }
+
+    async fn list<'long>(connection: &'long Self::Connection) -> sqlx::Result<Vec<Self>>
+    where
+        Self: 'long,
+    {
+        sqlx::query_as::<_, AuditEventModel>("SELECT * FROM audit_events")
+            .fetch_all(connection)
+            .await
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditFilter {
+    pub entity: Option<String>,
+    /// Id of the audited entity.
+    pub id: Option<u64>,
+    pub actor_id: Option<u64>,
+    pub request_id: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+impl Paginate for AuditEventModel {
+    type Filter = AuditFilter;
+
+    const SORT_COLUMNS: &'static [&'static str] = &["id", "created_at"];
+
+    async fn paginate<'long>(
+        page: &'long PageRequest,
+        filter: &'long AuditFilter,
+        connection_pool: &'long MySqlPool,
+    ) -> sqlx::Result<Page<Self>>
+    where
+        Self: 'long,
+    {
+        fetch_page(
+            "audit_events",
+            page,
+            |query| {
+                if let Some(entity) = &filter.entity {
+                    query.push(" AND entity_type = ").push_bind(entity.clone());
+                }
+                if let Some(id) = filter.id {
+                    query.push(" AND entity_id = ").push_bind(id);
+                }
+                if let Some(actor_id) = filter.actor_id {
+                    query.push(" AND actor_id = ").push_bind(actor_id);
+                }
+                if let Some(request_id) = &filter.request_id {
+                    query
+                        .push(" AND request_id = ")
+                        .push_bind(request_id.clone());
+                }
+                if let Some(from) = filter.created_from {
+                    query.push(" AND created_at >= ").push_bind(from);
+                }
+                if let Some(to) = filter.created_to {
+                    query.push(" AND created_at <= ").push_bind(to);
+                }
+            },
+            |event: &AuditEventModel, column| match column {
+                "created_at" => {
+                    SortValue::Text(event.created_at.format("%Y-%m-%d %H:%M:%S%.6f").to_string())
+                }
+                _ => SortValue::Integer(event.id),
+            },
+            connection_pool,
+        )
+        .await
+    }
+}
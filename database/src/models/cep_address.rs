@@ -0,0 +1,111 @@
+use cep_service::responses::cep::CepResponse;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, MySqlPool, QueryBuilder};
+
+use crate::pagination::like_prefix;
+
+/// A row of the locally imported CEP dataset, stored without the CEP hyphen.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CepAddressModel {
+    pub cep: String,
+    pub logradouro: String,
+    pub complemento: String,
+    pub bairro: String,
+    pub localidade: String,
+    pub uf: String,
+    pub ibge: String,
+    pub gia: String,
+    pub ddd: String,
+    pub siafi: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl CepAddressModel {
+    pub async fn get_by_cep(cep: &str, connection: &MySqlPool) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as::<_, CepAddressModel>(
+            r#"
+            SELECT cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, created_at, updated_at
+            FROM cep_addresses
+            WHERE cep = ?
+            "#,
+        )
+        .bind(cep.replace('-', ""))
+        .fetch_optional(connection)
+        .await
+    }
+
+    /// Finds the CEPs of a street, matching the street name partially. Comparisons follow the
+    /// table collation, so they ignore case and accents.
+    pub async fn search(
+        uf: &str,
+        city: &str,
+        street: &str,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as::<_, CepAddressModel>(
+            r#"
+            SELECT cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, created_at, updated_at
+            FROM cep_addresses
+            WHERE uf = ? AND localidade = ? AND logradouro LIKE ?
+            ORDER BY cep
+            LIMIT 50
+            "#,
+        )
+        .bind(uf)
+        .bind(city)
+        .bind(format!("%{}", like_prefix(street)))
+        .fetch_all(connection)
+        .await
+    }
+
+    /// Inserts the records in batches, replacing the address of CEPs that already exist.
+    pub async fn upsert_many(records: &[CepResponse], connection: &MySqlPool) -> sqlx::Result<u64> {
+        let mut affected = 0;
+        for batch in records.chunks(1000) {
+            let mut query = QueryBuilder::new(
+                "INSERT INTO cep_addresses (cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi) ",
+            );
+            query.push_values(batch, |mut row, record| {
+                row.push_bind(record.cep.replace('-', ""))
+                    .push_bind(&record.logradouro)
+                    .push_bind(&record.complemento)
+                    .push_bind(&record.bairro)
+                    .push_bind(&record.localidade)
+                    .push_bind(&record.uf)
+                    .push_bind(&record.ibge)
+                    .push_bind(&record.gia)
+                    .push_bind(&record.ddd)
+                    .push_bind(&record.siafi);
+            });
+            query.push(
+                r#"
+                ON DUPLICATE KEY UPDATE
+                    logradouro = VALUES(logradouro), complemento = VALUES(complemento),
+                    bairro = VALUES(bairro), localidade = VALUES(localidade), uf = VALUES(uf),
+                    ibge = VALUES(ibge), gia = VALUES(gia), ddd = VALUES(ddd), siafi = VALUES(siafi)
+                "#,
+            );
+            affected += query.build().execute(connection).await?.rows_affected();
+        }
+        Ok(affected)
+    }
+}
+
+impl From<CepAddressModel> for CepResponse {
+    fn from(model: CepAddressModel) -> Self {
+        Self {
+            cep: format!("{}-{}", &model.cep[0..5], &model.cep[5..8]),
+            logradouro: model.logradouro,
+            complemento: model.complemento,
+            bairro: model.bairro,
+            localidade: model.localidade,
+            uf: model.uf,
+            ibge: model.ibge,
+            gia: model.gia,
+            ddd: model.ddd,
+            siafi: model.siafi,
+        }
+    }
+}
@@ -1,9 +1,24 @@
 use async_trait::async_trait;
+use cep_service::{responses::cep::CepResponse, structs::cep::Cep};
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{error::BoxDynError, FromRow, MySqlPool};
+use sqlx::{error::BoxDynError, FromRow, MySql, MySqlConnection, MySqlPool, QueryBuilder};
 
-use crate::traits::{database::Database, persist::Persist};
+use crate::{
+    duplicates::{phonetic_key, DuplicateScore, Identity, POSSIBLE_DUPLICATE_SCORE},
+    models::{
+        address::{AddressModel, AddressType, NewAddressModel},
+        audit_event::AuditContext,
+        person_version::PersonVersionModel,
+    },
+    pagination::{
+        fetch_page, like_prefix, Page, PageRequest, SortValue, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+    },
+    traits::{
+        audit::Audit, database::Database, paginate::Paginate, persist::Persist,
+        soft_delete::SoftDelete,
+    },
+};
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct PersonModel {
@@ -11,22 +26,162 @@ pub struct PersonModel {
     pub name: String,
     pub mothers_name: String,
     pub fathers_name: String,
-    pub cep: String,
+    /// Empty once the person's last address is deleted.
+    pub cep: Option<Cep>,
+    #[sqlx(flatten)]
+    pub address: ResolvedAddress,
     pub birth_date: NaiveDate,
+    /// Bumped on every update, guarding against lost updates.
+    pub version: u32,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// The address a person's CEP resolved to, stored alongside the person so reads never depend
+/// on a CEP provider. `address_verified_at` is empty until the CEP has been resolved once.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct ResolvedAddress {
+    pub logradouro: String,
+    pub complemento: String,
+    pub bairro: String,
+    pub localidade: String,
+    pub uf: String,
+    pub ibge: String,
+    pub gia: String,
+    pub ddd: String,
+    pub siafi: String,
+    pub address_verified_at: Option<DateTime<Utc>>,
+}
+
+impl ResolvedAddress {
+    pub fn is_verified(&self) -> bool {
+        self.address_verified_at.is_some()
+    }
+
+    /// Same address fields, whenever each was verified.
+    pub fn same_address(&self, other: &ResolvedAddress) -> bool {
+        self.logradouro == other.logradouro
+            && self.complemento == other.complemento
+            && self.bairro == other.bairro
+            && self.localidade == other.localidade
+            && self.uf == other.uf
+            && self.ibge == other.ibge
+            && self.gia == other.gia
+            && self.ddd == other.ddd
+            && self.siafi == other.siafi
+    }
+}
+
+/// A `persons` row as the `query_as!` macros read it, which cannot fill a flattened field.
+struct PersonRow {
+    id: u64,
+    name: String,
+    mothers_name: String,
+    fathers_name: String,
+    cep: Option<Cep>,
+    logradouro: String,
+    complemento: String,
+    bairro: String,
+    localidade: String,
+    uf: String,
+    ibge: String,
+    gia: String,
+    ddd: String,
+    siafi: String,
+    address_verified_at: Option<DateTime<Utc>>,
+    birth_date: NaiveDate,
+    version: u32,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<PersonRow> for PersonModel {
+    fn from(row: PersonRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            mothers_name: row.mothers_name,
+            fathers_name: row.fathers_name,
+            cep: row.cep,
+            address: ResolvedAddress {
+                logradouro: row.logradouro,
+                complemento: row.complemento,
+                bairro: row.bairro,
+                localidade: row.localidade,
+                uf: row.uf,
+                ibge: row.ibge,
+                gia: row.gia,
+                ddd: row.ddd,
+                siafi: row.siafi,
+                address_verified_at: row.address_verified_at,
+            },
+            birth_date: row.birth_date,
+            version: row.version,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+impl From<CepResponse> for ResolvedAddress {
+    fn from(address: CepResponse) -> Self {
+        Self {
+            logradouro: address.logradouro,
+            complemento: address.complemento,
+            bairro: address.bairro,
+            localidade: address.localidade,
+            uf: address.uf,
+            ibge: address.ibge,
+            gia: address.gia,
+            ddd: address.ddd,
+            siafi: address.siafi,
+            address_verified_at: Some(Utc::now()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NewPersonModel {
     pub name: String,
     pub mothers_name: String,
     pub fathers_name: String,
-    pub cep: String,
+    pub cep: Cep,
+    #[serde(default)]
+    pub uf: Option<String>,
+    #[serde(default)]
+    pub number: String,
+    #[serde(default)]
+    pub complement: String,
     pub birth_date: NaiveDate,
     pub age: u8,
 }
 
+/// Full replacement of a person's own fields, used by `PUT` and as the base of merge patches.
+/// `cep` is only empty in the base of a person without addresses, and is required on save.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdatePersonModel {
+    pub name: String,
+    pub mothers_name: String,
+    pub fathers_name: String,
+    pub cep: Option<Cep>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uf: Option<String>,
+    pub birth_date: NaiveDate,
+}
+
+impl From<&PersonModel> for UpdatePersonModel {
+    fn from(person: &PersonModel) -> Self {
+        Self {
+            name: person.name.clone(),
+            mothers_name: person.mothers_name.clone(),
+            fathers_name: person.fathers_name.clone(),
+            cep: person.cep,
+            uf: None,
+            birth_date: person.birth_date,
+        }
+    }
+}
+
 #[async_trait]
 impl Database<MySqlPool> for PersonModel {
     type Connection = MySqlPool;
@@ -36,80 +191,587 @@ impl Database<MySqlPool> for PersonModel {
     where
         Self: 'long,
     {
-        let person = sqlx::query_as!(PersonModel, "SELECT * FROM persons WHERE id = ?", id)
-            .fetch_one(connection)
-            .await?;
-
-        Ok(person)
+        Self::get_in(id, &mut *connection.acquire().await?).await
     }
 
     async fn list<'long>(connection: &'long Self::Connection) -> sqlx::Result<Vec<Self>>
     where
         Self: 'long,
     {
-        let result = sqlx::query_as!(PersonModel, "SELECT * FROM persons")
+        let result = sqlx::query_as!(
+            PersonRow,
+            r#"
+                SELECT id, name, mothers_name, fathers_name, cep as "cep: Cep", logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, version, created_at, updated_at
+                FROM persons
+                WHERE deleted_at IS NULL
+            "#
+        )
+        .fetch_all(connection)
+        .await?;
+
+        Ok(result.into_iter().map(PersonModel::from).collect())
+    }
+}
+
+impl PersonModel {
+    /// Like `get`, but also finds soft-deleted persons, for purging and restoring.
+    pub async fn get_with_deleted(id: u64, connection: &MySqlPool) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            PersonRow,
+            r#"
+                SELECT id, name, mothers_name, fathers_name, cep as "cep: Cep", logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, version, created_at, updated_at
+                FROM persons
+                WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map(PersonModel::from)
+    }
+
+    pub fn identity(&self) -> Identity<'_> {
+        Identity {
+            name: &self.name,
+            mothers_name: &self.mothers_name,
+            birth_date: self.birth_date,
+            cep: self.cep,
+        }
+    }
+
+    /// Other persons scoring at least `POSSIBLE_DUPLICATE_SCORE` against this one, best first.
+    /// Candidates share a birth date, a phonetic name or mother's name, or the first name's key.
+    pub async fn find_duplicates(
+        &self,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<(PersonModel, DuplicateScore)>> {
+        let name_key = phonetic_key(&self.name);
+        let first_name_key = name_key.split(' ').next().unwrap_or_default();
+        let candidates = sqlx::query_as!(
+            PersonRow,
+            r#"
+                SELECT id, name, mothers_name, fathers_name, cep as "cep: Cep", logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, version, created_at, updated_at
+                FROM persons
+                WHERE id <> ? AND deleted_at IS NULL
+                AND (birth_date = ? OR name_phonetic = ? OR mothers_name_phonetic = ? OR name_phonetic LIKE ?)
+                LIMIT 200
+            "#,
+            self.id,
+            self.birth_date,
+            &name_key,
+            phonetic_key(&self.mothers_name),
+            like_prefix(&format!("{} ", first_name_key)),
+        )
+        .fetch_all(connection)
+        .await?;
+        let identity = self.identity();
+        let mut duplicates: Vec<(PersonModel, DuplicateScore)> = candidates
+            .into_iter()
+            .map(PersonModel::from)
+            .map(|candidate| {
+                let score = DuplicateScore::between(&identity, &candidate.identity());
+                (candidate, score)
+            })
+            .filter(|(_, score)| score.score >= POSSIBLE_DUPLICATE_SCORE)
+            .collect();
+        duplicates.sort_by(|(_, left), (_, right)| right.score.total_cmp(&left.score));
+        Ok(duplicates)
+    }
+
+    /// Recomputes the phonetic keys of every person, e.g. after the key algorithm changes.
+    /// Soft-deleted persons are included so that they are current again once restored.
+    pub async fn reindex_phonetics(connection: &MySqlPool) -> sqlx::Result<u64> {
+        let persons = sqlx::query!("SELECT id, name, mothers_name FROM persons")
             .fetch_all(connection)
             .await?;
+        let mut updated = 0;
+        for person in persons {
+            let result = sqlx::query!(
+                "UPDATE persons SET name_phonetic = ?, mothers_name_phonetic = ? WHERE id = ?",
+                phonetic_key(&person.name),
+                phonetic_key(&person.mothers_name),
+                person.id,
+            )
+            .execute(connection)
+            .await?;
+            updated += result.rows_affected();
+        }
+        Ok(updated)
+    }
 
-        Ok(result)
+    /// Inserts the person together with their primary address in one transaction, so that a
+    /// failed address insert leaves no person behind.
+    pub async fn insert_with_address(
+        &self,
+        address: NewAddressModel,
+        audit: &AuditContext,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        let mut transaction = connection.begin().await?;
+        let person = self.insert_audited_in(audit, &mut transaction).await?;
+        AddressModel::new(person.id, address, self.address.clone())
+            .insert_audited_in(audit, &mut transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(person)
     }
-}
 
-#[async_trait]
-impl Persist for PersonModel {
-    async fn insert<'long>(
-        &'long self,
-        database_connection: &'long Self::Connection,
-    ) -> sqlx::Result<Self::Model>
-    where
-        Self: 'long,
-    {
+    /// Updates the person and moves their primary address to `cep` in one transaction, adding a
+    /// primary address when none is left, so that a failed address write keeps the old person.
+    pub async fn update_with_address(
+        &self,
+        cep: Cep,
+        audit: &AuditContext,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        let mut transaction = connection.begin().await?;
+        let person = self.update_audited_in(audit, &mut transaction).await?;
+        match AddressModel::get_primary(person.id, &mut transaction).await? {
+            Some(primary) => {
+                AddressModel {
+                    cep,
+                    resolved: self.address.clone(),
+                    ..primary
+                }
+                .update_audited_in(audit, &mut transaction)
+                .await?;
+            }
+            None => {
+                let address = NewAddressModel {
+                    address_type: AddressType::Residential,
+                    cep,
+                    number: String::new(),
+                    complement: String::new(),
+                    is_primary: true,
+                };
+                AddressModel::new(person.id, address, self.address.clone())
+                    .insert_audited_in(audit, &mut transaction)
+                    .await?;
+            }
+        }
+        let person = Self::get_in(person.id, &mut transaction).await?;
+        transaction.commit().await?;
+        Ok(person)
+    }
+
+    /// Inserts the person and records it as version 1, attributed to `creator_id`.
+    pub async fn insert_by(
+        &self,
+        creator_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
         let result = sqlx::query!(
-            "INSERT INTO persons (name, mothers_name, fathers_name, cep, birth_date, created_at) VALUES (?, ?, ?, ?, ?, ?)",
-            self.name,
-            self.mothers_name,
-            self.fathers_name,
+            r#"
+                INSERT INTO persons (name, name_phonetic, mothers_name, mothers_name_phonetic, fathers_name, cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            &self.name,
+            phonetic_key(&self.name),
+            &self.mothers_name,
+            phonetic_key(&self.mothers_name),
+            &self.fathers_name,
             self.cep,
+            &self.address.logradouro,
+            &self.address.complemento,
+            &self.address.bairro,
+            &self.address.localidade,
+            &self.address.uf,
+            &self.address.ibge,
+            &self.address.gia,
+            &self.address.ddd,
+            &self.address.siafi,
+            self.address.address_verified_at,
             self.birth_date,
             self.created_at,
-        ).execute(database_connection).await?;
-        Ok(Self::get(result.last_insert_id() as u64, database_connection).await?)
+        )
+        .execute(&mut *connection)
+        .await?;
+        let id = result.last_insert_id();
+        PersonVersionModel::record(id, creator_id, &mut *connection).await?;
+        Self::get_in(id, connection).await
     }
 
-    async fn update<'long>(
-        &'long self,
-        database_connection: &'long Self::Connection,
-    ) -> sqlx::Result<Self::Model>
-    where
-        Self: 'long,
-    {
+    /// Updates the person and records the result as a new version attributed to `editor_id`.
+    /// Fails with `RowNotFound` when the stored person is no longer at `self.version`.
+    pub async fn update_by(
+        &self,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
         let result = sqlx::query!(
-            "UPDATE persons SET name = ?, mothers_name = ?, fathers_name = ?, cep = ?, birth_date = ?, updated_at = ? WHERE id = ?",
-            self.name,
-            self.mothers_name,
-            self.fathers_name,
+            r#"
+                UPDATE persons
+                SET name = ?, name_phonetic = ?, mothers_name = ?, mothers_name_phonetic = ?, fathers_name = ?, cep = ?, logradouro = ?, complemento = ?, bairro = ?, localidade = ?, uf = ?, ibge = ?, gia = ?, ddd = ?, siafi = ?, address_verified_at = ?, birth_date = ?, updated_at = ?, version = version + 1
+                WHERE id = ? AND version = ?
+            "#,
+            &self.name,
+            phonetic_key(&self.name),
+            &self.mothers_name,
+            phonetic_key(&self.mothers_name),
+            &self.fathers_name,
             self.cep,
+            &self.address.logradouro,
+            &self.address.complemento,
+            &self.address.bairro,
+            &self.address.localidade,
+            &self.address.uf,
+            &self.address.ibge,
+            &self.address.gia,
+            &self.address.ddd,
+            &self.address.siafi,
+            self.address.address_verified_at,
             self.birth_date,
             self.updated_at,
             self.id,
-        ).execute(database_connection).await?;
-        Ok(Self::get(result.last_insert_id() as u64, database_connection).await?)
+            self.version,
+        )
+        .execute(&mut *connection)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        PersonVersionModel::record(self.id, editor_id, &mut *connection).await?;
+        Self::get_in(self.id, connection).await
+    }
+
+    /// Applies `changes` on top of this person, keeping the stored address unless replaced.
+    pub fn with_changes(self, changes: UpdatePersonModel, address: ResolvedAddress) -> Self {
+        Self {
+            name: changes.name,
+            mothers_name: changes.mothers_name,
+            fathers_name: changes.fathers_name,
+            cep: changes.cep,
+            address,
+            birth_date: changes.birth_date,
+            updated_at: Some(Utc::now()),
+            ..self
+        }
+    }
+
+    /// Bumps `updated_at` when a child row goes away, which its `on update` default cannot see.
+    pub async fn touch(id: u64, connection: &mut MySqlConnection) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE persons SET updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            id
+        )
+        .execute(connection)
+        .await?;
+        Ok(())
+    }
+
+    /// Mirrors the primary address, or its absence, on the persons row as a new version
+    /// attributed to `editor_id`. Nothing is written when the CEP and address already match.
+    pub async fn mirror_primary(
+        id: u64,
+        cep: Option<Cep>,
+        address: &ResolvedAddress,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<()> {
+        let person: PersonModel = sqlx::query_as!(
+            PersonRow,
+            r#"
+                SELECT id, name, mothers_name, fathers_name, cep as "cep: Cep", logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, version, created_at, updated_at
+                FROM persons
+                WHERE id = ?
+                FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_one(&mut *connection)
+        .await?
+        .into();
+        if person.cep == cep && person.address.same_address(address) {
+            return Ok(());
+        }
+        sqlx::query!(
+            r#"
+                UPDATE persons
+                SET cep = ?, logradouro = ?, complemento = ?, bairro = ?, localidade = ?, uf = ?, ibge = ?, gia = ?, ddd = ?, siafi = ?, address_verified_at = ?, version = version + 1
+                WHERE id = ?
+            "#,
+            cep,
+            &address.logradouro,
+            &address.complemento,
+            &address.bairro,
+            &address.localidade,
+            &address.uf,
+            &address.ibge,
+            &address.gia,
+            &address.ddd,
+            &address.siafi,
+            address.address_verified_at,
+            id,
+        )
+        .execute(&mut *connection)
+        .await?;
+        PersonVersionModel::record(id, editor_id, &mut *connection).await
+    }
+
+    /// Stores a freshly resolved address without touching the rest of the person, recorded as
+    /// a new version attributed to `editor_id`.
+    pub async fn update_address(
+        id: u64,
+        address: &ResolvedAddress,
+        editor_id: Option<u64>,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE persons
+                SET logradouro = ?, complemento = ?, bairro = ?, localidade = ?, uf = ?, ibge = ?, gia = ?, ddd = ?, siafi = ?, address_verified_at = ?, version = version + 1
+                WHERE id = ?
+            "#,
+            &address.logradouro,
+            &address.complemento,
+            &address.bairro,
+            &address.localidade,
+            &address.uf,
+            &address.ibge,
+            &address.gia,
+            &address.ddd,
+            &address.siafi,
+            address.address_verified_at,
+            id,
+        )
+        .execute(&mut *connection)
+        .await?;
+        PersonVersionModel::record(id, editor_id, &mut *connection).await
     }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PersonFilter {
+    pub name: Option<String>,
+    pub cep: Option<Cep>,
+    pub birth_date_from: Option<NaiveDate>,
+    pub birth_date_to: Option<NaiveDate>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+}
+
+/// Free-text search over the person's names and, optionally, their annotations.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PersonSearch {
+    pub q: String,
+    #[serde(default)]
+    pub include_annotations: bool,
+    pub limit: Option<u32>,
+}
+
+/// InnoDB ignores shorter words unless `innodb_ft_min_token_size` is lowered.
+const MIN_SEARCH_TERM_LENGTH: usize = 3;
+
+impl PersonSearch {
+    /// Boolean-mode expression requiring every word as a prefix, e.g. `+joao* +silva*`. Short
+    /// words such as "da" are dropped since the index never holds them.
+    pub fn expression(&self) -> Option<String> {
+        let terms: Vec<String> = self
+            .q
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| term.chars().count() >= MIN_SEARCH_TERM_LENGTH)
+            .map(|term| format!("+{}*", term))
+            .collect();
+        match terms.is_empty() {
+            true => None,
+            false => Some(terms.join(" ")),
+        }
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct PersonSearchHit {
+    #[sqlx(flatten)]
+    pub person: PersonModel,
+    pub relevance: f64,
+}
+
+impl PersonModel {
+    /// Ranks persons by FULLTEXT relevance. The columns use an accent- and case-insensitive
+    /// collation, so "joao silva" finds "João da Silva".
+    pub async fn search(
+        search: &PersonSearch,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<PersonSearchHit>> {
+        let Some(expression) = search.expression() else {
+            return Ok(Vec::new());
+        };
+        let mut query = QueryBuilder::<MySql>::new(
+            r#"
+            SELECT persons.*, SUM(matches.relevance) AS relevance
+            FROM persons
+            JOIN (
+                SELECT id AS person_id, MATCH(name, mothers_name, fathers_name) AGAINST ("#,
+        );
+        query
+            .push_bind(expression.clone())
+            .push(" IN BOOLEAN MODE) AS relevance FROM persons WHERE MATCH(name, mothers_name, fathers_name) AGAINST (")
+            .push_bind(expression.clone())
+            .push(" IN BOOLEAN MODE)");
+        if search.include_annotations {
+            query
+                .push(" UNION ALL SELECT person_id, MATCH(title, description) AGAINST (")
+                .push_bind(expression.clone())
+                .push(
+                    " IN BOOLEAN MODE) FROM annotations WHERE MATCH(title, description) AGAINST (",
+                )
+                .push_bind(expression)
+                .push(" IN BOOLEAN MODE) AND deleted_at IS NULL");
+        }
+        query
+            .push(
+                r#"
+            ) AS matches ON matches.person_id = persons.id
+            WHERE persons.deleted_at IS NULL
+            GROUP BY persons.id
+            ORDER BY relevance DESC, persons.id
+            LIMIT "#,
+            )
+            .push_bind(search.limit());
+        query
+            .build_query_as::<PersonSearchHit>()
+            .fetch_all(connection)
+            .await
+    }
+}
 
-    async fn delete<'long>(
-        &'long self,
-        database_connection: &'long Self::Connection,
-    ) -> Result<(), BoxDynError>
+#[async_trait]
+impl Paginate for PersonModel {
+    type Filter = PersonFilter;
+
+    const SORT_COLUMNS: &'static [&'static str] = &["id", "name", "birth_date", "created_at"];
+
+    async fn paginate<'long>(
+        page: &'long PageRequest,
+        filter: &'long PersonFilter,
+        connection: &'long MySqlPool,
+    ) -> sqlx::Result<Page<Self>>
     where
         Self: 'long,
     {
-        let result = sqlx::query!("DELETE FROM persons WHERE id = ?", self.id,)
-            .execute(database_connection)
+        fetch_page(
+            "persons",
+            page,
+            |query| {
+                query.push(" AND deleted_at IS NULL");
+                if let Some(name) = &filter.name {
+                    query.push(" AND name LIKE ").push_bind(like_prefix(name));
+                }
+                if let Some(cep) = filter.cep {
+                    query.push(" AND cep = ").push_bind(cep);
+                }
+                if let Some(from) = filter.birth_date_from {
+                    query.push(" AND birth_date >= ").push_bind(from);
+                }
+                if let Some(to) = filter.birth_date_to {
+                    query.push(" AND birth_date <= ").push_bind(to);
+                }
+                if let Some(from) = filter.created_from {
+                    query.push(" AND created_at >= ").push_bind(from);
+                }
+                if let Some(to) = filter.created_to {
+                    query.push(" AND created_at <= ").push_bind(to);
+                }
+            },
+            |person: &PersonModel, column| match column {
+                "name" => SortValue::Text(person.name.clone()),
+                "birth_date" => SortValue::Text(person.birth_date.format("%Y-%m-%d").to_string()),
+                "created_at" => SortValue::Text(
+                    person
+                        .created_at
+                        .format("%Y-%m-%d %H:%M:%S%.6f")
+                        .to_string(),
+                ),
+                _ => SortValue::Integer(person.id),
+            },
+            connection,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Persist for PersonModel {
+    async fn get_in(id: u64, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        sqlx::query_as!(
+            PersonRow,
+            r#"
+                SELECT id, name, mothers_name, fathers_name, cep as "cep: Cep", logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, version, created_at, updated_at
+                FROM persons
+                WHERE id = ? AND deleted_at IS NULL
+            "#,
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map(PersonModel::from)
+    }
+
+    async fn insert_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        self.insert_by(None, connection).await
+    }
+
+    async fn update_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        self.update_by(None, connection).await
+    }
+
+    async fn delete_in(&self, connection: &mut MySqlConnection) -> Result<(), BoxDynError> {
+        // Purges the person for good. Annotations have no cascading foreign key, so they go
+        // first; addresses, revisions and merge tombstones cascade.
+        sqlx::query!("DELETE FROM annotations WHERE person_id = ?", self.id)
+            .execute(&mut *connection)
             .await?;
-        match result.rows_affected() {
-            1 => Ok(()),
-            _ => Err("Error deleting person".into()),
+        let result = sqlx::query!("DELETE FROM persons WHERE id = ?", self.id)
+            .execute(&mut *connection)
+            .await?;
+        if result.rows_affected() != 1 {
+            return Err("Error deleting person".into());
         }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SoftDelete for PersonModel {
+    const TABLE: &'static str = "persons";
+
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+#[async_trait]
+impl Audit for PersonModel {
+    const ENTITY: &'static str = "person";
+
+    fn entity_id(&self) -> u64 {
+        self.id
+    }
+
+    async fn write_insert(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.insert_by(audit.actor_id, connection).await
+    }
+
+    async fn write_update(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.update_by(audit.actor_id, connection).await
     }
 }
 
@@ -122,8 +784,10 @@ impl TryFrom<NewPersonModel> for PersonModel {
             name: value.name,
             mothers_name: value.mothers_name,
             fathers_name: value.fathers_name,
-            cep: value.cep,
+            cep: Some(value.cep),
+            address: ResolvedAddress::default(),
             birth_date: value.birth_date,
+            version: 1,
             created_at: Utc::now(),
             updated_at: None,
         };
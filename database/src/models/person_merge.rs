@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow, MySqlPool};
+
+use crate::{
+    models::{
+        audit_event::{AuditAction, AuditContext, AuditEventModel},
+        person::PersonModel,
+    },
+    traits::audit::Audit,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeChoice {
+    /// Keep the surviving person's value.
+    #[default]
+    Target,
+    /// Take the merged person's value.
+    Source,
+}
+
+/// Which record each field is taken from. The CEP carries its resolved address along.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MergeChoices {
+    pub name: MergeChoice,
+    pub mothers_name: MergeChoice,
+    pub fathers_name: MergeChoice,
+    pub cep: MergeChoice,
+    pub birth_date: MergeChoice,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeRequest {
+    pub source_id: u64,
+    #[serde(default)]
+    pub fields: MergeChoices,
+}
+
+/// Tombstone of a merged person: redirects `merged_id` to `survivor_id` and keeps who merged
+/// it, the field choices and a snapshot of the record as it was before the merge.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PersonMergeModel {
+    pub id: u64,
+    pub survivor_id: u64,
+    pub merged_id: u64,
+    pub merged_by: Option<u64>,
+    pub choices: Json<MergeChoices>,
+    pub snapshot: Json<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PersonMergeModel {
+    /// The person a merged id now points to, if it was merged.
+    pub async fn survivor_of(merged_id: u64, connection: &MySqlPool) -> sqlx::Result<Option<u64>> {
+        sqlx::query_scalar("SELECT survivor_id FROM person_merges WHERE merged_id = ?")
+            .bind(merged_id)
+            .fetch_optional(connection)
+            .await
+    }
+
+    pub async fn list_by_survivor(
+        survivor_id: u64,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as::<_, PersonMergeModel>(
+            "SELECT * FROM person_merges WHERE survivor_id = ? ORDER BY id",
+        )
+        .bind(survivor_id)
+        .fetch_all(connection)
+        .await
+    }
+
+    /// Folds `request.source_id` into `target_id` in one transaction: applies the field
+    /// choices, moves addresses and annotations, repoints older tombstones, records the merge
+    /// and deletes the source row, logging the merge against both persons. Fails with
+    /// `RowNotFound` when the target is no longer at `target_version`.
+    pub async fn merge(
+        target_id: u64,
+        target_version: u32,
+        request: &MergeRequest,
+        audit: &AuditContext,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<PersonModel> {
+        let mut transaction = connection.begin().await?;
+        let target = sqlx::query_as::<_, PersonModel>(
+            "SELECT * FROM persons WHERE id = ? AND version = ? AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(target_id)
+        .bind(target_version)
+        .fetch_one(&mut *transaction)
+        .await?;
+        let source = sqlx::query_as::<_, PersonModel>(
+            "SELECT * FROM persons WHERE id = ? AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(request.source_id)
+        .fetch_one(&mut *transaction)
+        .await?;
+        let snapshot = serde_json::to_value(&source).unwrap_or_default();
+
+        let choices = &request.fields;
+        let pick = |choice: MergeChoice, target: String, source: String| match choice {
+            MergeChoice::Target => target,
+            MergeChoice::Source => source,
+        };
+        let name = pick(choices.name, target.name.clone(), source.name.clone());
+        let mothers_name = pick(
+            choices.mothers_name,
+            target.mothers_name.clone(),
+            source.mothers_name.clone(),
+        );
+        let fathers_name = pick(
+            choices.fathers_name,
+            target.fathers_name.clone(),
+            source.fathers_name.clone(),
+        );
+        let birth_date = match choices.birth_date {
+            MergeChoice::Target => target.birth_date,
+            MergeChoice::Source => source.birth_date,
+        };
+        let (cep, address) = match choices.cep {
+            MergeChoice::Target => (target.cep, &target.address),
+            MergeChoice::Source => (source.cep, &source.address),
+        };
+
+        PersonModel {
+            id: target.id,
+            name,
+            mothers_name,
+            fathers_name,
+            cep,
+            address: address.clone(),
+            birth_date,
+            version: target.version,
+            created_at: target.created_at,
+            updated_at: Some(Utc::now()),
+        }
+        .update_by(audit.actor_id, &mut transaction)
+        .await?;
+
+        // The survivor keeps a single primary address: the source's one when its CEP was chosen.
+        let primary_owner = match choices.cep {
+            MergeChoice::Target => target.id,
+            MergeChoice::Source => source.id,
+        };
+        let primary: Option<u64> = sqlx::query_scalar(
+            "SELECT id FROM addresses WHERE person_id = ? AND is_primary LIMIT 1",
+        )
+        .bind(primary_owner)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        sqlx::query(
+            "UPDATE addresses SET person_id = ?, version = version + 1 WHERE person_id = ?",
+        )
+        .bind(target.id)
+        .bind(source.id)
+        .execute(&mut *transaction)
+        .await?;
+        if let Some(primary) = primary {
+            sqlx::query(
+                "UPDATE addresses SET version = version + (is_primary <> (id = ?)), is_primary = (id = ?) WHERE person_id = ?",
+            )
+            .bind(primary)
+            .bind(primary)
+            .bind(target.id)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        sqlx::query("UPDATE annotations SET person_id = ? WHERE person_id = ?")
+            .bind(target.id)
+            .bind(source.id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE person_merges SET survivor_id = ? WHERE survivor_id = ?")
+            .bind(target.id)
+            .bind(source.id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO person_merges (survivor_id, merged_id, merged_by, choices, snapshot)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(target.id)
+        .bind(source.id)
+        .bind(audit.actor_id)
+        .bind(Json(choices))
+        .bind(Json(snapshot))
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query("DELETE FROM persons WHERE id = ?")
+            .bind(source.id)
+            .execute(&mut *transaction)
+            .await?;
+        let survivor = sqlx::query_as::<_, PersonModel>("SELECT * FROM persons WHERE id = ?")
+            .bind(target.id)
+            .fetch_one(&mut *transaction)
+            .await?;
+        AuditEventModel::record(
+            audit,
+            PersonModel::ENTITY,
+            target.id,
+            AuditAction::Merged,
+            Some(target.audit_document()),
+            Some(survivor.audit_document()),
+            &mut *transaction,
+        )
+        .await?;
+        AuditEventModel::record(
+            audit,
+            PersonModel::ENTITY,
+            source.id,
+            AuditAction::Merged,
+            Some(source.audit_document()),
+            Some(serde_json::json!({ "survivor_id": target.id })),
+            &mut *transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(survivor)
+    }
+}
@@ -0,0 +1,123 @@
+use cep_service::structs::cep::Cep;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, MySql, MySqlPool};
+
+use crate::models::person::{PersonModel, ResolvedAddress, UpdatePersonModel};
+
+/// Columns copied from `persons` into each version.
+const VERSIONED_COLUMNS: &str = "name, mothers_name, fathers_name, cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date";
+
+/// A person's own fields as they stood from `created_at` until the next version.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PersonVersionModel {
+    pub id: u64,
+    pub person_id: u64,
+    pub version: u32,
+    pub name: String,
+    pub mothers_name: String,
+    pub fathers_name: String,
+    pub cep: Option<Cep>,
+    #[sqlx(flatten)]
+    pub address: ResolvedAddress,
+    pub birth_date: NaiveDate,
+    pub changed_by: Option<u64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&PersonVersionModel> for UpdatePersonModel {
+    fn from(version: &PersonVersionModel) -> Self {
+        Self {
+            name: version.name.clone(),
+            mothers_name: version.mothers_name.clone(),
+            fathers_name: version.fathers_name.clone(),
+            cep: version.cep,
+            uf: None,
+            birth_date: version.birth_date,
+        }
+    }
+}
+
+impl PersonVersionModel {
+    /// Snapshots the person's stored row under its current `version`, attributed to `changed_by`.
+    pub async fn record<'e, E>(
+        person_id: u64,
+        changed_by: Option<u64>,
+        executor: E,
+    ) -> sqlx::Result<()>
+    where
+        E: Executor<'e, Database = MySql>,
+    {
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO person_versions (person_id, version, {columns}, changed_by)
+            SELECT id, version, {columns}, ?
+            FROM persons WHERE id = ?
+            "#,
+            columns = VERSIONED_COLUMNS
+        ))
+        .bind(changed_by)
+        .bind(person_id)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_by_person(person_id: u64, connection: &MySqlPool) -> sqlx::Result<Vec<Self>> {
+        sqlx::query_as::<_, PersonVersionModel>(
+            "SELECT * FROM person_versions WHERE person_id = ? ORDER BY version DESC",
+        )
+        .bind(person_id)
+        .fetch_all(connection)
+        .await
+    }
+
+    pub async fn get_by_person(
+        person_id: u64,
+        version: u32,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        sqlx::query_as::<_, PersonVersionModel>(
+            "SELECT * FROM person_versions WHERE person_id = ? AND version = ?",
+        )
+        .bind(person_id)
+        .bind(version)
+        .fetch_one(connection)
+        .await
+    }
+
+    /// The version that was current at `at`, if the person had one by then.
+    pub async fn as_of(
+        person_id: u64,
+        at: DateTime<Utc>,
+        connection: &MySqlPool,
+    ) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as::<_, PersonVersionModel>(
+            r#"
+            SELECT * FROM person_versions
+            WHERE person_id = ? AND created_at <= ?
+            ORDER BY version DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(person_id)
+        .bind(at)
+        .fetch_optional(connection)
+        .await
+    }
+
+    /// The person as they were at this version, keeping `current`'s id and creation date.
+    pub fn into_person(self, current: PersonModel) -> PersonModel {
+        PersonModel {
+            name: self.name,
+            mothers_name: self.mothers_name,
+            fathers_name: self.fathers_name,
+            cep: self.cep,
+            address: self.address,
+            birth_date: self.birth_date,
+            version: self.version,
+            updated_at: Some(self.created_at),
+            ..current
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use crate::traits::{database::Database, login::Login, persist::Persist, token::Token};
+use crate::{
+    pagination::{fetch_page, like_prefix, Page, PageRequest, SortValue},
+    traits::{
+        audit::Audit, database::Database, login::Login, paginate::Paginate, persist::Persist,
+        soft_delete::SoftDelete, token::Token,
+    },
+};
 use anyhow::bail;
 use argon2::{
     password_hash::{rand_core::OsRng, SaltString},
@@ -8,7 +14,8 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use http::StatusCode;
 use serde::{Deserialize, Serialize};
-use sqlx::{error::BoxDynError, FromRow, MySqlPool};
+use serde_json::Value;
+use sqlx::{error::BoxDynError, FromRow, MySqlConnection, MySqlPool};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -23,6 +30,10 @@ pub struct UserModel {
     #[serde(skip_serializing)]
     #[schema(example = "password")]
     password: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub version: u32,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
@@ -49,30 +60,35 @@ impl UserModel {
     pub fn get_password(&self) -> &str {
         &self.password
     }
-}
-
-#[async_trait]
-impl Database<MySqlPool> for UserModel {
-    type Connection = MySqlPool;
-    type Model = Self;
 
-    async fn get<'long>(id: u64, connection_pool: &'long Self::Connection) -> sqlx::Result<Self>
-    where
-        Self: 'long,
-    {
+    /// Like `get`, but also finds soft-deleted users, for restoring.
+    pub async fn get_with_deleted(id: u64, connection: &MySqlPool) -> sqlx::Result<Self> {
         let user = sqlx::query_as!(
             UserModel,
             r#"
-                SELECT id, api_token, name, email, password, created_at, updated_at
+                SELECT id, api_token, name, email, password, is_admin as "is_admin: bool", version, created_at, updated_at
                 FROM users
                 WHERE id = ?
             "#,
             id
         )
-        .fetch_one(connection_pool)
+        .fetch_one(connection)
         .await?;
         Ok(user)
     }
+}
+
+#[async_trait]
+impl Database<MySqlPool> for UserModel {
+    type Connection = MySqlPool;
+    type Model = Self;
+
+    async fn get<'long>(id: u64, connection_pool: &'long Self::Connection) -> sqlx::Result<Self>
+    where
+        Self: 'long,
+    {
+        Self::get_in(id, &mut *connection_pool.acquire().await?).await
+    }
 
     async fn list<'long>(connection_pool: &'long Self::Connection) -> sqlx::Result<Vec<Self>>
     where
@@ -81,8 +97,9 @@ impl Database<MySqlPool> for UserModel {
         let result = sqlx::query_as!(
             UserModel,
             r#"
-                SELECT id, api_token, name, email, password, created_at, updated_at
+                SELECT id, api_token, name, email, password, is_admin as "is_admin: bool", version, created_at, updated_at
                 FROM users
+                WHERE deleted_at IS NULL
             "#
         )
         .fetch_all(connection_pool)
@@ -91,15 +108,78 @@ impl Database<MySqlPool> for UserModel {
     }
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserFilter {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+}
+
 #[async_trait]
-impl Persist for UserModel {
-    async fn insert<'long>(
-        &'long self,
-        database_connection: &'long Self::Connection,
-    ) -> sqlx::Result<Self::Model>
+impl Paginate for UserModel {
+    type Filter = UserFilter;
+
+    const SORT_COLUMNS: &'static [&'static str] = &["id", "name", "email", "created_at"];
+
+    async fn paginate<'long>(
+        page: &'long PageRequest,
+        filter: &'long UserFilter,
+        connection_pool: &'long MySqlPool,
+    ) -> sqlx::Result<Page<Self>>
     where
         Self: 'long,
     {
+        fetch_page(
+            "users",
+            page,
+            |query| {
+                query.push(" AND deleted_at IS NULL");
+                if let Some(name) = &filter.name {
+                    query.push(" AND name LIKE ").push_bind(like_prefix(name));
+                }
+                if let Some(email) = &filter.email {
+                    query.push(" AND email = ").push_bind(email.clone());
+                }
+                if let Some(from) = filter.created_from {
+                    query.push(" AND created_at >= ").push_bind(from);
+                }
+                if let Some(to) = filter.created_to {
+                    query.push(" AND created_at <= ").push_bind(to);
+                }
+            },
+            |user: &UserModel, column| match column {
+                "name" => SortValue::Text(user.name.clone()),
+                "email" => SortValue::Text(user.email.clone()),
+                "created_at" => {
+                    SortValue::Text(user.created_at.format("%Y-%m-%d %H:%M:%S%.6f").to_string())
+                }
+                _ => SortValue::Integer(user.id),
+            },
+            connection_pool,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Persist for UserModel {
+    async fn get_in(id: u64, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
+        let user = sqlx::query_as!(
+            UserModel,
+            r#"
+                SELECT id, api_token, name, email, password, is_admin as "is_admin: bool", version, created_at, updated_at
+                FROM users
+                WHERE id = ? AND deleted_at IS NULL
+            "#,
+            id
+        )
+        .fetch_one(connection)
+        .await?;
+        Ok(user)
+    }
+
+    async fn insert_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
         let result = sqlx::query!(
             r#"
                     INSERT INTO users (name, email, password, api_token)
@@ -110,35 +190,33 @@ impl Persist for UserModel {
             &self.password,
             &self.api_token.to_string(),
         )
-        .execute(database_connection)
+        .execute(&mut *connection)
         .await?;
-        Ok(Self::get(result.last_insert_id() as u64, database_connection).await?)
+        Self::get_in(result.last_insert_id(), connection).await
     }
 
-    async fn update<'long>(
-        &'long self,
-        database_connection: &'long Self::Connection,
-    ) -> sqlx::Result<Self::Model> {
+    async fn update_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self> {
         let result = sqlx::query!(
             r#"
                     UPDATE users
-                    SET name = ?, email = ?, password = ?
-                    WHERE id = ?
+                    SET name = ?, email = ?, password = ?, version = version + 1
+                    WHERE id = ? AND version = ?
                 "#,
             &self.name,
             &self.email,
             &self.password,
             &self.id,
+            &self.version,
         )
-        .execute(database_connection)
+        .execute(&mut *connection)
         .await?;
-        Ok(Self::get(result.last_insert_id() as u64, database_connection).await?)
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Self::get_in(self.id, connection).await
     }
 
-    async fn delete<'long>(
-        &'long self,
-        database_connection: &'long Self::Connection,
-    ) -> Result<(), BoxDynError> {
+    async fn delete_in(&self, connection: &mut MySqlConnection) -> Result<(), BoxDynError> {
         let result = sqlx::query!(
             r#"
                     DELETE FROM users
@@ -146,7 +224,7 @@ impl Persist for UserModel {
                 "#,
             &self.id,
         )
-        .execute(database_connection)
+        .execute(connection)
         .await?;
         match result.rows_affected() {
             1 => Ok(()),
@@ -155,15 +233,45 @@ impl Persist for UserModel {
     }
 }
 
+#[async_trait]
+impl SoftDelete for UserModel {
+    const TABLE: &'static str = "users";
+
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+#[async_trait]
+impl Audit for UserModel {
+    const ENTITY: &'static str = "user";
+
+    fn entity_id(&self) -> u64 {
+        self.id
+    }
+
+    fn audit_document(&self) -> Value {
+        let mut document = serde_json::to_value(self).unwrap_or_default();
+        if let Some(document) = document.as_object_mut() {
+            document.remove("api_token");
+        }
+        document
+    }
+}
+
 #[async_trait]
 impl Login for UserModel {
     async fn login(body: LoginModel, connection_pool: &Self::Connection) -> anyhow::Result<Self> {
         let user = sqlx::query_as!(
             UserModel,
             r#"
-                SELECT id, api_token, name, email, password, created_at, updated_at
+                SELECT id, api_token, name, email, password, is_admin as "is_admin: bool", version, created_at, updated_at
                 FROM users
-                WHERE email = ?
+                WHERE email = ? AND deleted_at IS NULL
             "#,
             body.email,
         )
@@ -214,9 +322,9 @@ impl Token for UserModel {
         let user = sqlx::query_as!(
             UserModel,
             r#"
-                SELECT id, api_token, name, email, password, created_at, updated_at
+                SELECT id, api_token, name, email, password, is_admin as "is_admin: bool", version, created_at, updated_at
                 FROM users
-                WHERE api_token = ?
+                WHERE api_token = ? AND deleted_at IS NULL
             "#,
             uuid.to_string(),
         )
@@ -241,6 +349,8 @@ impl TryFrom<NewUserModel> for UserModel {
             name: new_user.name,
             email: new_user.email,
             password: password_hash,
+            is_admin: false,
+            version: 1,
             created_at: Utc::now(),
             updated_at: None,
         };
@@ -0,0 +1,201 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sqlx::{mysql::MySqlRow, FromRow, MySql, MySqlPool, QueryBuilder};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn keyword(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// Paging parameters shared by every list endpoint. A `cursor` takes precedence over `offset`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageRequest {
+    pub limit: Option<u32>,
+    pub offset: Option<u64>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+impl PageRequest {
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset.unwrap_or(0)
+    }
+
+    pub fn sort(&self) -> &str {
+        self.sort.as_deref().unwrap_or("id")
+    }
+
+    /// Checks the sort column against the model's whitelist and that the cursor can be decoded
+    /// and was issued for the same sort.
+    pub fn validate(&self, sort_columns: &[&str]) -> Result<(), String> {
+        if !sort_columns.contains(&self.sort()) {
+            return Err(format!(
+                "Cannot sort by {}, expected one of: {}",
+                self.sort(),
+                sort_columns.join(", ")
+            ));
+        }
+        if let Some(cursor) = &self.cursor {
+            let Some(Cursor(sort, order, ..)) = Cursor::decode(cursor) else {
+                return Err("Invalid cursor".to_string());
+            };
+            if sort != self.sort() || order != self.order {
+                return Err(format!(
+                    "The cursor was issued for sort={}&order={}; repeat it with the same sort",
+                    sort,
+                    order.keyword().to_lowercase()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SortValue {
+    Integer(u64),
+    Text(String),
+}
+
+/// Position after the last row of a page: the sort it was issued for, then the row's sort
+/// value and id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Cursor(String, SortOrder, SortValue, u64);
+
+impl Cursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn with_items<U>(self, items: Vec<U>) -> Page<U> {
+        Page {
+            items,
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// Escapes `%`, `_` and `\` so user input can be used as a `LIKE` prefix.
+pub fn like_prefix(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{}%", escaped)
+}
+
+/// Runs a filtered, sorted page query against `table`. `push_filters` appends ` AND ...`
+/// conditions, and `sort_value` reads a row's value for a sortable column, `id` included.
+pub(crate) async fn fetch_page<T, F, S>(
+    table: &str,
+    request: &PageRequest,
+    push_filters: F,
+    sort_value: S,
+    connection: &MySqlPool,
+) -> sqlx::Result<Page<T>>
+where
+    T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    F: Fn(&mut QueryBuilder<'_, MySql>),
+    S: Fn(&T, &str) -> SortValue,
+{
+    let sort = request.sort();
+    let order = request.order;
+    let limit = request.limit();
+    let cursor = request.cursor.as_deref().and_then(Cursor::decode);
+
+    let mut count =
+        QueryBuilder::<MySql>::new(format!("SELECT COUNT(*) FROM {} WHERE 1 = 1", table));
+    push_filters(&mut count);
+    let total: i64 = count.build_query_scalar().fetch_one(connection).await?;
+
+    let mut query = QueryBuilder::<MySql>::new(format!("SELECT * FROM {} WHERE 1 = 1", table));
+    push_filters(&mut query);
+    if let Some(Cursor(_, _, value, id)) = &cursor {
+        let comparison = match order {
+            SortOrder::Asc => ">",
+            SortOrder::Desc => "<",
+        };
+        query.push(format!(" AND ({}, id) {} (", sort, comparison));
+        match value {
+            SortValue::Integer(value) => query.push_bind(*value),
+            SortValue::Text(value) => query.push_bind(value.clone()),
+        };
+        query.push(", ").push_bind(*id).push(")");
+    }
+    query.push(format!(
+        " ORDER BY {} {}, id {}",
+        sort,
+        order.keyword(),
+        order.keyword()
+    ));
+    query.push(" LIMIT ").push_bind(limit + 1);
+    if cursor.is_none() {
+        query.push(" OFFSET ").push_bind(request.offset());
+    }
+    let mut items = query.build_query_as::<T>().fetch_all(connection).await?;
+
+    let next_cursor = match items.len() > limit as usize {
+        true => {
+            items.truncate(limit as usize);
+            items.last().and_then(|last| match sort_value(last, "id") {
+                SortValue::Integer(id) => {
+                    Some(Cursor(sort.to_string(), order, sort_value(last, sort), id).encode())
+                }
+                SortValue::Text(_) => None,
+            })
+        }
+        false => None,
+    };
+
+    Ok(Page {
+        items,
+        total: total as u64,
+        limit,
+        offset: cursor.is_none().then(|| request.offset()),
+        next_cursor,
+    })
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use cep_service::{
+    error::CepServiceError,
+    responses::cep::CepResponse,
+    structs::{cep::Cep, search::AddressQuery},
+    traits::retrievable::Retrievable,
+};
+use sqlx::MySqlPool;
+
+use crate::models::cep_address::CepAddressModel;
+
+/// Resolves CEPs from the `cep_addresses` table filled by the `import-ceps` command.
+#[derive(Debug, Clone)]
+pub struct DatabaseCepProvider {
+    connection: MySqlPool,
+}
+
+impl DatabaseCepProvider {
+    pub fn new(connection: MySqlPool) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl Retrievable for DatabaseCepProvider {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    async fn retrieve(&self, cep: &Cep) -> Result<CepResponse, CepServiceError> {
+        match CepAddressModel::get_by_cep(&cep.digits(), &self.connection).await {
+            Ok(Some(address)) => Ok(address.into()),
+            Ok(None) => Err(CepServiceError::NotFound),
+            Err(error) => Err(CepServiceError::transport(self.name(), error)),
+        }
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    async fn search(&self, query: &AddressQuery) -> Result<Vec<CepResponse>, CepServiceError> {
+        CepAddressModel::search(
+            query.uf.trim(),
+            query.city.trim(),
+            query.street.trim(),
+            &self.connection,
+        )
+        .await
+        .map(|addresses| addresses.into_iter().map(CepResponse::from).collect())
+        .map_err(|error| CepServiceError::transport(self.name(), error))
+    }
+}
@@ -1,4 +1,7 @@
+pub mod audit;
 pub mod database;
 pub mod login;
+pub mod paginate;
 pub mod persist;
+pub mod soft_delete;
 pub mod token;
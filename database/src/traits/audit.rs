@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{error::BoxDynError, MySqlConnection, MySqlPool};
+
+use crate::{
+    models::audit_event::{AuditAction, AuditContext, AuditEventModel},
+    traits::{database::Database, persist::Persist, soft_delete::SoftDelete},
+};
+
+/// Wraps the `Persist` and `SoftDelete` writes so that each one leaves an `audit_events` row,
+/// written in the same transaction as the change it describes.
+#[async_trait]
+pub trait Audit:
+    Persist
+    + Database<MySqlPool, Connection = MySqlPool, Model = Self>
+    + Serialize
+    + Send
+    + Sync
+    + Sized
+{
+    /// Entity type the events are filed under.
+    const ENTITY: &'static str;
+
+    fn entity_id(&self) -> u64;
+
+    /// The document diffed into the log; override to keep secrets out of it.
+    fn audit_document(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// The insert behind `insert_audited`, for models that track their author themselves.
+    async fn write_insert(
+        &self,
+        _audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.insert_in(connection).await
+    }
+
+    /// The update behind `update_audited`, for models that track their editor themselves.
+    async fn write_update(
+        &self,
+        _audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        self.update_in(connection).await
+    }
+
+    /// The delete behind `delete_audited`, for models that track their editor themselves.
+    async fn write_delete(
+        &self,
+        _audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> Result<(), BoxDynError> {
+        self.delete_in(connection).await
+    }
+
+    async fn insert_audited(
+        &self,
+        audit: &AuditContext,
+        database_connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        let mut transaction = database_connection.begin().await?;
+        let created = self.insert_audited_in(audit, &mut transaction).await?;
+        transaction.commit().await?;
+        Ok(created)
+    }
+
+    /// `insert_audited` on a connection the caller may hold a transaction on.
+    async fn insert_audited_in(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        let created = self.write_insert(audit, connection).await?;
+        AuditEventModel::record(
+            audit,
+            Self::ENTITY,
+            created.entity_id(),
+            AuditAction::Created,
+            None,
+            Some(created.audit_document()),
+            &mut *connection,
+        )
+        .await?;
+        Ok(created)
+    }
+
+    async fn update_audited(
+        &self,
+        audit: &AuditContext,
+        database_connection: &MySqlPool,
+    ) -> sqlx::Result<Self> {
+        let mut transaction = database_connection.begin().await?;
+        let updated = self.update_audited_in(audit, &mut transaction).await?;
+        transaction.commit().await?;
+        Ok(updated)
+    }
+
+    /// `update_audited` on a connection the caller may hold a transaction on.
+    async fn update_audited_in(
+        &self,
+        audit: &AuditContext,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<Self> {
+        let before = Self::get_in(self.entity_id(), connection).await?;
+        let updated = self.write_update(audit, connection).await?;
+        AuditEventModel::record(
+            audit,
+            Self::ENTITY,
+            updated.entity_id(),
+            AuditAction::Updated,
+            Some(before.audit_document()),
+            Some(updated.audit_document()),
+            &mut *connection,
+        )
+        .await?;
+        Ok(updated)
+    }
+
+    /// `Persist::delete`, logged as a purge since the row is gone for good.
+    async fn delete_audited(
+        &self,
+        audit: &AuditContext,
+        database_connection: &MySqlPool,
+    ) -> Result<(), BoxDynError> {
+        let mut transaction = database_connection.begin().await?;
+        self.write_delete(audit, &mut transaction).await?;
+        AuditEventModel::record(
+            audit,
+            Self::ENTITY,
+            self.entity_id(),
+            AuditAction::Purged,
+            Some(self.audit_document()),
+            None,
+            &mut *transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn soft_delete_audited(
+        &self,
+        audit: &AuditContext,
+        database_connection: &MySqlPool,
+    ) -> Result<(), BoxDynError>
+    where
+        Self: SoftDelete,
+    {
+        let mut transaction = database_connection.begin().await?;
+        self.soft_delete(&mut transaction).await?;
+        AuditEventModel::record(
+            audit,
+            Self::ENTITY,
+            self.entity_id(),
+            AuditAction::Deleted,
+            Some(self.audit_document()),
+            None,
+            &mut *transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Restores a soft-deleted row; `None` when no deleted row has that id at `version`.
+    async fn restore_audited(
+        id: u64,
+        version: u32,
+        audit: &AuditContext,
+        database_connection: &MySqlPool,
+    ) -> sqlx::Result<Option<Self>>
+    where
+        Self: SoftDelete,
+    {
+        let mut transaction = database_connection.begin().await?;
+        if !Self::restore(id, version, &mut transaction).await? {
+            return Ok(None);
+        }
+        let restored = Self::get_in(id, &mut transaction).await?;
+        AuditEventModel::record(
+            audit,
+            Self::ENTITY,
+            id,
+            AuditAction::Restored,
+            None,
+            Some(restored.audit_document()),
+            &mut *transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+        Ok(Some(restored))
+    }
+}
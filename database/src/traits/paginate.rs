@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use sqlx::MySqlPool;
+
+use crate::{
+    pagination::{Page, PageRequest},
+    traits::database::Database,
+};
+
+#[async_trait]
+pub trait Paginate: for<'long> Database<MySqlPool> {
+    type Filter: Send + Sync;
+
+    /// Columns accepted by `PageRequest::sort`.
+    const SORT_COLUMNS: &'static [&'static str];
+
+    async fn paginate<'long>(
+        page: &'long PageRequest,
+        filter: &'long Self::Filter,
+        database_connection: &'long MySqlPool,
+    ) -> sqlx::Result<Page<Self::Model>>
+    where
+        Self: 'long,
+        Self::Model: 'long;
+}
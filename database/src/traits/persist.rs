@@ -1,29 +1,59 @@
 use crate::traits::database::Database;
 use async_trait::async_trait;
-use sqlx::{error::BoxDynError, MySqlPool};
+use sqlx::{error::BoxDynError, MySqlConnection, MySqlPool};
 
+/// Writes of a model. Each one is implemented on a single connection (the `*_in` methods) so
+/// that callers can run it inside a transaction of their own; the pool methods run it in a
+/// transaction of its own.
 #[async_trait]
 pub trait Persist: for<'long> Database<MySqlPool> {
+    /// Reads a row back on the connection a write ran on, seeing its uncommitted changes.
+    async fn get_in(id: u64, connection: &mut MySqlConnection) -> sqlx::Result<Self::Model>;
+
+    async fn insert_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self::Model>;
+
+    async fn update_in(&self, connection: &mut MySqlConnection) -> sqlx::Result<Self::Model>;
+
+    async fn delete_in(&self, connection: &mut MySqlConnection) -> Result<(), BoxDynError>;
+
     async fn insert<'long>(
         &'long self,
         database_connection: &'long MySqlPool,
     ) -> sqlx::Result<Self::Model>
     where
-        Self: 'long,
-        Self::Model: 'long;
+        Self: Sync + 'long,
+        Self::Model: Send + 'long,
+    {
+        let mut transaction = database_connection.begin().await?;
+        let model = self.insert_in(&mut transaction).await?;
+        transaction.commit().await?;
+        Ok(model)
+    }
 
     async fn update<'long>(
         &'long self,
         database_connection: &'long MySqlPool,
     ) -> sqlx::Result<Self::Model>
     where
-        Self: 'long,
-        Self::Model: 'long;
+        Self: Sync + 'long,
+        Self::Model: Send + 'long,
+    {
+        let mut transaction = database_connection.begin().await?;
+        let model = self.update_in(&mut transaction).await?;
+        transaction.commit().await?;
+        Ok(model)
+    }
 
     async fn delete<'long>(
         &'long self,
         database_connection: &'long MySqlPool,
     ) -> Result<(), BoxDynError>
     where
-        Self: 'long;
+        Self: Sync + 'long,
+    {
+        let mut transaction = database_connection.begin().await?;
+        self.delete_in(&mut transaction).await?;
+        transaction.commit().await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use sqlx::{error::BoxDynError, MySqlConnection};
+
+use crate::traits::persist::Persist;
+
+/// Hides rows behind a `deleted_at` timestamp instead of removing them. `Persist::delete`
+/// stays a hard delete, used to purge.
+#[async_trait]
+pub trait SoftDelete: Persist {
+    /// Table holding the `deleted_at` column.
+    const TABLE: &'static str;
+
+    fn id(&self) -> u64;
+
+    fn version(&self) -> u32;
+
+    /// Fails with `RowNotFound` when the row is already deleted or no longer at
+    /// `self.version()`.
+    async fn soft_delete(&self, connection: &mut MySqlConnection) -> Result<(), BoxDynError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = CURRENT_TIMESTAMP WHERE id = ? AND version = ? AND deleted_at IS NULL",
+            Self::TABLE
+        ))
+        .bind(self.id())
+        .bind(self.version())
+        .execute(&mut *connection)
+        .await?;
+        if result.rows_affected() != 1 {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+        self.after_soft_delete(connection).await?;
+        Ok(())
+    }
+
+    /// Runs in the same transaction as `soft_delete`, for rows whose removal changes another.
+    async fn after_soft_delete(&self, _connection: &mut MySqlConnection) -> sqlx::Result<()> {
+        Ok(())
+    }
+
+    /// Clears `deleted_at`; `false` when no deleted row has that id at `version`.
+    async fn restore(
+        id: u64,
+        version: u32,
+        connection: &mut MySqlConnection,
+    ) -> sqlx::Result<bool> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = NULL WHERE id = ? AND version = ? AND deleted_at IS NOT NULL",
+            Self::TABLE
+        ))
+        .bind(id)
+        .bind(version)
+        .execute(connection)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1 @@
+DROP TABLE cep_addresses;
\ No newline at end of file
@@ -0,0 +1,15 @@
+CREATE TABLE cep_addresses (
+    cep char(8) not null primary key,
+    logradouro varchar(255) not null default '',
+    complemento varchar(255) not null default '',
+    bairro varchar(255) not null default '',
+    localidade varchar(255) not null,
+    uf char(2) not null,
+    ibge varchar(7) not null default '',
+    gia varchar(4) not null default '',
+    ddd varchar(2) not null default '',
+    siafi varchar(4) not null default '',
+    created_at timestamp not null default current_timestamp,
+    updated_at timestamp null default null on update current_timestamp,
+    index cep_addresses_uf_localidade_index (uf, localidade)
+) engine=innodb default charset=utf8mb4 collate=utf8mb4_unicode_ci;
@@ -0,0 +1,12 @@
+ALTER TABLE persons
+    DROP INDEX persons_address_verified_at_index,
+    DROP COLUMN logradouro,
+    DROP COLUMN complemento,
+    DROP COLUMN bairro,
+    DROP COLUMN localidade,
+    DROP COLUMN uf,
+    DROP COLUMN ibge,
+    DROP COLUMN gia,
+    DROP COLUMN ddd,
+    DROP COLUMN siafi,
+    DROP COLUMN address_verified_at;
\ No newline at end of file
@@ -0,0 +1,12 @@
+ALTER TABLE persons
+    ADD COLUMN logradouro varchar(255) not null default '' AFTER cep,
+    ADD COLUMN complemento varchar(255) not null default '' AFTER logradouro,
+    ADD COLUMN bairro varchar(255) not null default '' AFTER complemento,
+    ADD COLUMN localidade varchar(255) not null default '' AFTER bairro,
+    ADD COLUMN uf char(2) not null default '' AFTER localidade,
+    ADD COLUMN ibge varchar(7) not null default '' AFTER uf,
+    ADD COLUMN gia varchar(4) not null default '' AFTER ibge,
+    ADD COLUMN ddd varchar(2) not null default '' AFTER gia,
+    ADD COLUMN siafi varchar(4) not null default '' AFTER ddd,
+    ADD COLUMN address_verified_at timestamp null default null AFTER siafi,
+    ADD INDEX persons_address_verified_at_index (address_verified_at);
@@ -0,0 +1,27 @@
+CREATE TABLE addresses (
+    id bigint(20) unsigned not null auto_increment primary key,
+    person_id bigint(20) unsigned not null,
+    type enum('residential', 'work', 'mailing') not null default 'residential',
+    cep char(8) not null,
+    number varchar(20) not null default '',
+    complement varchar(255) not null default '',
+    is_primary boolean not null default false,
+    logradouro varchar(255) not null default '',
+    complemento varchar(255) not null default '',
+    bairro varchar(255) not null default '',
+    localidade varchar(255) not null default '',
+    uf char(2) not null default '',
+    ibge varchar(7) not null default '',
+    gia varchar(4) not null default '',
+    ddd varchar(2) not null default '',
+    siafi varchar(4) not null default '',
+    address_verified_at timestamp null default null,
+    created_at timestamp not null default current_timestamp,
+    updated_at timestamp null default null on update current_timestamp,
+    index addresses_person_id_is_primary_index (person_id, is_primary),
+    constraint addresses_person_id_foreign foreign key (person_id) references persons (id) on delete cascade
+) engine=innodb default charset=utf8mb4 collate=utf8mb4_unicode_ci;
+
+INSERT INTO addresses (person_id, type, cep, is_primary, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at)
+SELECT id, 'residential', cep, true, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at
+FROM persons;
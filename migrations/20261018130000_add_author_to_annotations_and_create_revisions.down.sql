@@ -0,0 +1,5 @@
+DROP TABLE annotation_revisions;
+
+ALTER TABLE annotations
+    DROP FOREIGN KEY annotations_author_id_foreign,
+    DROP COLUMN author_id;
\ No newline at end of file
@@ -0,0 +1,15 @@
+ALTER TABLE annotations
+    ADD COLUMN author_id bigint(20) unsigned null default null AFTER person_id,
+    ADD CONSTRAINT annotations_author_id_foreign FOREIGN KEY (author_id) REFERENCES users (id) on delete set null;
+
+CREATE TABLE annotation_revisions (
+    id bigint(20) unsigned not null auto_increment primary key,
+    annotation_id bigint(20) unsigned not null,
+    editor_id bigint(20) unsigned null default null,
+    title varchar(255) not null,
+    description text not null,
+    created_at timestamp not null default current_timestamp,
+    index annotation_revisions_annotation_id_index (annotation_id),
+    constraint annotation_revisions_annotation_id_foreign foreign key (annotation_id) references annotations (id) on delete cascade,
+    constraint annotation_revisions_editor_id_foreign foreign key (editor_id) references users (id) on delete set null
+) engine=innodb default charset=utf8mb4 collate=utf8mb4_unicode_ci;
@@ -0,0 +1,5 @@
+ALTER TABLE annotations
+    DROP INDEX annotations_title_description_fulltext;
+
+ALTER TABLE persons
+    DROP INDEX persons_names_fulltext;
\ No newline at end of file
@@ -0,0 +1,5 @@
+ALTER TABLE persons
+    ADD FULLTEXT INDEX persons_names_fulltext (name, mothers_name, fathers_name);
+
+ALTER TABLE annotations
+    ADD FULLTEXT INDEX annotations_title_description_fulltext (title, description);
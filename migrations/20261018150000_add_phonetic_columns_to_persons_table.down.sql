@@ -0,0 +1,6 @@
+ALTER TABLE persons
+    DROP INDEX persons_birth_date_index,
+    DROP INDEX persons_mothers_name_phonetic_index,
+    DROP INDEX persons_name_phonetic_index,
+    DROP COLUMN mothers_name_phonetic,
+    DROP COLUMN name_phonetic;
\ No newline at end of file
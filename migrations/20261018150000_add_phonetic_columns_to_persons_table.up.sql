@@ -0,0 +1,6 @@
+ALTER TABLE persons
+    ADD COLUMN name_phonetic varchar(255) not null default '' AFTER name,
+    ADD COLUMN mothers_name_phonetic varchar(255) not null default '' AFTER mothers_name,
+    ADD INDEX persons_name_phonetic_index (name_phonetic),
+    ADD INDEX persons_mothers_name_phonetic_index (mothers_name_phonetic),
+    ADD INDEX persons_birth_date_index (birth_date);
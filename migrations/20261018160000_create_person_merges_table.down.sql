@@ -0,0 +1 @@
+DROP TABLE person_merges;
\ No newline at end of file
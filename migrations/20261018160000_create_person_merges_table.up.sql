@@ -0,0 +1,12 @@
+CREATE TABLE person_merges (
+    id bigint(20) unsigned not null auto_increment primary key,
+    survivor_id bigint(20) unsigned not null,
+    merged_id bigint(20) unsigned not null unique,
+    merged_by bigint(20) unsigned null default null,
+    choices json not null,
+    snapshot json not null,
+    created_at timestamp not null default current_timestamp,
+    index person_merges_survivor_id_index (survivor_id),
+    constraint person_merges_survivor_id_foreign foreign key (survivor_id) references persons (id) on delete cascade,
+    constraint person_merges_merged_by_foreign foreign key (merged_by) references users (id) on delete set null
+) engine=innodb default charset=utf8mb4 collate=utf8mb4_unicode_ci;
@@ -0,0 +1,12 @@
+ALTER TABLE users
+    DROP INDEX users_deleted_at_index,
+    DROP COLUMN deleted_at,
+    DROP COLUMN is_admin;
+
+ALTER TABLE annotations
+    DROP INDEX annotations_deleted_at_index,
+    DROP COLUMN deleted_at;
+
+ALTER TABLE persons
+    DROP INDEX persons_deleted_at_index,
+    DROP COLUMN deleted_at;
\ No newline at end of file
@@ -0,0 +1,12 @@
+ALTER TABLE persons
+    ADD COLUMN deleted_at timestamp null default null,
+    ADD INDEX persons_deleted_at_index (deleted_at);
+
+ALTER TABLE annotations
+    ADD COLUMN deleted_at timestamp null default null,
+    ADD INDEX annotations_deleted_at_index (deleted_at);
+
+ALTER TABLE users
+    ADD COLUMN is_admin boolean not null default false AFTER password,
+    ADD COLUMN deleted_at timestamp null default null,
+    ADD INDEX users_deleted_at_index (deleted_at);
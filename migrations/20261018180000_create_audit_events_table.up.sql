@@ -0,0 +1,15 @@
+CREATE TABLE audit_events (
+    id bigint(20) unsigned not null auto_increment primary key,
+    actor_id bigint(20) unsigned null default null,
+    entity_type varchar(32) not null,
+    entity_id bigint(20) unsigned not null,
+    action enum('created', 'updated', 'deleted', 'restored', 'purged', 'merged') not null,
+    old_values json null default null,
+    new_values json null default null,
+    request_id varchar(64) null default null,
+    created_at timestamp(6) not null default current_timestamp(6),
+    index audit_events_entity_index (entity_type, entity_id, created_at),
+    index audit_events_actor_id_index (actor_id),
+    index audit_events_request_id_index (request_id),
+    constraint audit_events_actor_id_foreign foreign key (actor_id) references users (id) on delete set null
+) engine=innodb default charset=utf8mb4 collate=utf8mb4_unicode_ci;
@@ -0,0 +1 @@
+DROP TABLE person_versions;
\ No newline at end of file
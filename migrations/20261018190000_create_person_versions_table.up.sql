@@ -0,0 +1,30 @@
+CREATE TABLE person_versions (
+    id bigint(20) unsigned not null auto_increment primary key,
+    person_id bigint(20) unsigned not null,
+    version int unsigned not null,
+    name varchar(255) not null,
+    mothers_name varchar(255) not null,
+    fathers_name varchar(255) not null,
+    cep char(8) not null,
+    logradouro varchar(255) not null default '',
+    complemento varchar(255) not null default '',
+    bairro varchar(255) not null default '',
+    localidade varchar(255) not null default '',
+    uf char(2) not null default '',
+    ibge varchar(7) not null default '',
+    gia varchar(4) not null default '',
+    ddd varchar(2) not null default '',
+    siafi varchar(4) not null default '',
+    address_verified_at timestamp null default null,
+    birth_date date not null,
+    changed_by bigint(20) unsigned null default null,
+    created_at timestamp(6) not null default current_timestamp(6),
+    unique index person_versions_person_id_version_unique (person_id, version),
+    index person_versions_person_id_created_at_index (person_id, created_at),
+    constraint person_versions_person_id_foreign foreign key (person_id) references persons (id) on delete cascade,
+    constraint person_versions_changed_by_foreign foreign key (changed_by) references users (id) on delete set null
+) engine=innodb default charset=utf8mb4 collate=utf8mb4_unicode_ci;
+
+INSERT INTO person_versions (person_id, version, name, mothers_name, fathers_name, cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, created_at)
+SELECT id, 1, name, mothers_name, fathers_name, cep, logradouro, complemento, bairro, localidade, uf, ibge, gia, ddd, siafi, address_verified_at, birth_date, created_at
+FROM persons;
@@ -0,0 +1,8 @@
+ALTER TABLE users
+    DROP COLUMN version;
+
+ALTER TABLE annotations
+    DROP COLUMN version;
+
+ALTER TABLE persons
+    DROP COLUMN version;
\ No newline at end of file
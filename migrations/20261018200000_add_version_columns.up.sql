@@ -0,0 +1,11 @@
+ALTER TABLE persons
+    ADD COLUMN version int unsigned not null default 1 AFTER birth_date;
+
+UPDATE persons
+SET version = (SELECT coalesce(max(person_versions.version), 1) FROM person_versions WHERE person_versions.person_id = persons.id);
+
+ALTER TABLE annotations
+    ADD COLUMN version int unsigned not null default 1 AFTER description;
+
+ALTER TABLE users
+    ADD COLUMN version int unsigned not null default 1 AFTER is_admin;
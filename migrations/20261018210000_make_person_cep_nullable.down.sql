@@ -0,0 +1,5 @@
+ALTER TABLE person_versions
+    MODIFY cep char(8) not null;
+
+ALTER TABLE persons
+    MODIFY cep char(8) not null;
\ No newline at end of file
@@ -0,0 +1,5 @@
+ALTER TABLE persons
+    MODIFY cep char(8) null;
+
+ALTER TABLE person_versions
+    MODIFY cep char(8) null;
@@ -0,0 +1,2 @@
+ALTER TABLE addresses
+    ADD COLUMN version int unsigned not null default 1 AFTER is_primary;